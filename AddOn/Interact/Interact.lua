@@ -7,15 +7,134 @@ StaticPopupDialogs['INTERACT_WARNING'] = {
    whileDead = true,
 }
 
-if not InteractNearest then
-   StaticPopup_Show('INTERACT_WARNING')
+StaticPopupDialogs['INTERACT_OUTDATED'] = {
+   text = '|cffffd200Interact|cffffffff: the DLL is older than the AddOn and is missing %s. Update interact.dll.',
+   button1 = 'Okay',
+   timeout = 0,
+   whileDead = true,
+}
+
+-- DLL functions this version of the AddOn calls
+local REQUIRED_FUNCTIONS = {
+   'InteractNearest',
+   'InteractSetRange',
+   'InteractGetRange',
+   'InteractSetPriority',
+   'InteractSetBlacklist',
+   'InteractSetTrace',
+   'InteractReloadConfig',
+   'InteractCommand',
+}
+
+-- Check the DLL is loaded and matches the AddOn
+local function CheckVersion()
+   if not InteractNearest then
+      StaticPopup_Show('INTERACT_WARNING')
+      return
+   end
+
+   if not InteractGetVersion then
+      StaticPopup_Show('INTERACT_OUTDATED', 'InteractGetVersion')
+      return
+   end
+
+   local version, caps = InteractGetVersion()
+   local missing = {}
+   for _, name in ipairs(REQUIRED_FUNCTIONS) do
+      if not caps.functions[name] then
+         table.insert(missing, name)
+      end
+   end
+   if table.getn(missing) > 0 then
+      StaticPopup_Show('INTERACT_OUTDATED', table.concat(missing, ', '))
+      return
+   end
+
+   local addonVersion = GetAddOnMetadata('Interact', 'Version')
+   if addonVersion and addonVersion ~= version then
+      DEFAULT_CHAT_FRAME:AddMessage('|cffffd200Interact:|r AddOn version ' .. addonVersion
+         .. ' does not match DLL version ' .. version .. '. Update both to the same release.')
+   end
 end
 
+CheckVersion()
+
+-- Settings saved per account. Only settings changed in the options panel are
+-- stored; anything else keeps the value from interact.toml.
+InteractDB = InteractDB or {}
+
 function Interact(autoloot)
     if not InteractNearest then
         StaticPopup_Show('INTERACT_WARNING')
         return
     end
 
+    if autoloot == nil then
+        autoloot = InteractDB.autoloot or 0
+    end
+
     InteractNearest(autoloot)
-end
\ No newline at end of file
+end
+
+function Interact_Print(message)
+    DEFAULT_CHAT_FRAME:AddMessage('|cffffd200Interact:|r ' .. message)
+end
+
+-- Report the error from a setter that returns true or nil, message
+local function check(ok, err)
+    if not ok then
+        Interact_Print(err)
+    end
+    return ok
+end
+
+-- Push the saved settings into the DLL
+function Interact_ApplySettings()
+    if not InteractNearest then
+        return
+    end
+
+    if InteractDB.distance or InteractDB.maxHeight then
+        -- Keep whatever was not saved, including both radii
+        local distance, maxHeight, unitRadius, objectRadius = InteractGetRange()
+        check(pcall(InteractSetRange, InteractDB.distance or distance,
+            InteractDB.maxHeight or maxHeight, unitRadius, objectRadius))
+    end
+    if InteractDB.priority then
+        check(InteractSetPriority(InteractDB.priority))
+    end
+    if InteractDB.blacklist then
+        check(InteractSetBlacklist(InteractDB.blacklist))
+    end
+    if InteractDB.trace ~= nil then
+        InteractSetTrace(InteractDB.trace and 1 or 0)
+    end
+end
+
+-- Forget the saved settings and go back to interact.toml
+function Interact_ResetSettings()
+    InteractDB = {}
+    if InteractReloadConfig then
+        check(InteractReloadConfig())
+    end
+end
+
+-- /interact subcommands are parsed and run by the DLL
+SLASH_INTERACT1 = '/interact'
+SlashCmdList['INTERACT'] = function(msg)
+    if not InteractCommand then
+        StaticPopup_Show('INTERACT_WARNING')
+        return
+    end
+
+    for line in string.gfind(InteractCommand(msg), '[^\n]+') do
+        Interact_Print(line)
+    end
+end
+
+local loader = CreateFrame('Frame')
+loader:RegisterEvent('VARIABLES_LOADED')
+loader:SetScript('OnEvent', function()
+    InteractDB = InteractDB or {}
+    Interact_ApplySettings()
+end)
@@ -0,0 +1,194 @@
+-- Options panel, opened with the "Interact options" key binding
+
+BINDING_NAME_INTERACT_OPTIONS = 'Interact options'
+
+local DEFAULT_DISTANCE = 5
+local DEFAULT_PRIORITY = 'lootable,gameobject,skinnable,alive,service'
+local DEFAULT_BLACKLIST = '179830,179831,179785,179786'
+
+local frame = CreateFrame('Frame', 'InteractOptionsFrame', UIParent)
+frame:SetWidth(360)
+frame:SetHeight(400)
+frame:SetPoint('CENTER', UIParent, 'CENTER', 0, 0)
+frame:SetFrameStrata('DIALOG')
+frame:SetBackdrop({
+    bgFile = 'Interface\\DialogFrame\\UI-DialogBox-Background',
+    edgeFile = 'Interface\\DialogFrame\\UI-DialogBox-Border',
+    tile = true, tileSize = 32, edgeSize = 32,
+    insets = { left = 11, right = 12, top = 12, bottom = 11 },
+})
+frame:EnableMouse(true)
+frame:SetMovable(true)
+frame:RegisterForDrag('LeftButton')
+frame:SetScript('OnDragStart', function() this:StartMoving() end)
+frame:SetScript('OnDragStop', function() this:StopMovingOrSizing() end)
+frame:Hide()
+tinsert(UISpecialFrames, 'InteractOptionsFrame')
+
+local title = frame:CreateFontString(nil, 'ARTWORK', 'GameFontNormalLarge')
+title:SetPoint('TOP', frame, 'TOP', 0, -20)
+title:SetText('Interact')
+
+-- -----------------------------------------------------------------------------
+-- Widgets
+-- -----------------------------------------------------------------------------
+
+local function CreateSlider(name, label, low, high, step, y, onChange)
+    local slider = CreateFrame('Slider', name, frame, 'OptionsSliderTemplate')
+    slider:SetWidth(300)
+    slider:SetPoint('TOP', frame, 'TOP', 0, y)
+    slider:SetMinMaxValues(low, high)
+    slider:SetValueStep(step)
+    getglobal(name .. 'Low'):SetText(low)
+    getglobal(name .. 'High'):SetText(high)
+    slider.label = label
+    slider:SetScript('OnValueChanged', function()
+        getglobal(this:GetName() .. 'Text'):SetText(this.label(this:GetValue()))
+        if not this.updating then
+            onChange(this:GetValue())
+        end
+    end)
+    return slider
+end
+
+local function CreateCheck(name, label, y, onChange)
+    local check = CreateFrame('CheckButton', name, frame, 'OptionsCheckButtonTemplate')
+    check:SetPoint('TOPLEFT', frame, 'TOPLEFT', 24, y)
+    getglobal(name .. 'Text'):SetText(label)
+    check:SetScript('OnClick', function()
+        onChange(this:GetChecked() and true or false)
+    end)
+    return check
+end
+
+local function CreateEditBox(name, label, y, onChange)
+    local text = frame:CreateFontString(nil, 'ARTWORK', 'GameFontNormalSmall')
+    text:SetPoint('TOPLEFT', frame, 'TOPLEFT', 30, y)
+    text:SetText(label)
+
+    local box = CreateFrame('EditBox', name, frame, 'InputBoxTemplate')
+    box:SetWidth(296)
+    box:SetHeight(20)
+    box:SetPoint('TOPLEFT', text, 'BOTTOMLEFT', 2, -4)
+    box:SetAutoFocus(false)
+    box:SetScript('OnEnterPressed', function()
+        this:ClearFocus()
+        onChange(this:GetText())
+    end)
+    box:SetScript('OnEscapePressed', function()
+        this:ClearFocus()
+        InteractOptions_Refresh()
+    end)
+    return box
+end
+
+-- -----------------------------------------------------------------------------
+-- Settings
+-- -----------------------------------------------------------------------------
+
+local distance = CreateSlider('InteractOptionsDistance',
+    function(value) return 'Range: ' .. value .. ' yd' end,
+    1, 10, 0.5, -60, function(value)
+        InteractDB.distance = value
+        Interact_ApplySettings()
+    end)
+
+local maxHeight = CreateSlider('InteractOptionsMaxHeight',
+    function(value)
+        if value == 0 then
+            return 'Height limit: off (3D distance)'
+        end
+        return 'Height limit: ' .. value .. ' yd'
+    end,
+    0, 10, 0.5, -110, function(value)
+        InteractDB.maxHeight = value
+        Interact_ApplySettings()
+    end)
+
+local autoloot = CreateCheck('InteractOptionsAutoloot', 'Auto-loot by default', -145,
+    function(checked)
+        InteractDB.autoloot = checked and 1 or 0
+    end)
+
+local trace = CreateCheck('InteractOptionsTrace', 'Verbose (trace) logging', -170,
+    function(checked)
+        InteractDB.trace = checked
+        Interact_ApplySettings()
+    end)
+
+local priority = CreateEditBox('InteractOptionsPriority', 'Priority order', -215,
+    function(text)
+        local ok, err = InteractSetPriority(text)
+        if ok then
+            InteractDB.priority = text
+        else
+            Interact_Print(err)
+        end
+    end)
+
+local blacklist = CreateEditBox('InteractOptionsBlacklist', 'Blacklisted object IDs', -265,
+    function(text)
+        local ok, err = InteractSetBlacklist(text)
+        if ok then
+            InteractDB.blacklist = text
+        else
+            Interact_Print(err)
+        end
+    end)
+
+local hint = frame:CreateFontString(nil, 'ARTWORK', 'GameFontHighlightSmall')
+hint:SetPoint('TOPLEFT', frame, 'TOPLEFT', 30, -315)
+hint:SetWidth(300)
+hint:SetJustifyH('LEFT')
+hint:SetText('Comma-separated. Press Enter to apply. Reset returns to interact.toml.')
+
+local reset = CreateFrame('Button', 'InteractOptionsReset', frame, 'UIPanelButtonTemplate')
+reset:SetWidth(100)
+reset:SetHeight(22)
+reset:SetPoint('BOTTOMLEFT', frame, 'BOTTOMLEFT', 24, 20)
+reset:SetText('Reset')
+reset:SetScript('OnClick', function()
+    Interact_ResetSettings()
+    InteractOptions_Refresh()
+end)
+
+local close = CreateFrame('Button', 'InteractOptionsClose', frame, 'UIPanelButtonTemplate')
+close:SetWidth(100)
+close:SetHeight(22)
+close:SetPoint('BOTTOMRIGHT', frame, 'BOTTOMRIGHT', -24, 20)
+close:SetText('Close')
+close:SetScript('OnClick', function() frame:Hide() end)
+
+-- -----------------------------------------------------------------------------
+-- Panel
+-- -----------------------------------------------------------------------------
+
+-- Show the saved settings, or the defaults for settings that were never changed
+function InteractOptions_Refresh()
+    distance.updating = true
+    distance:SetValue(InteractDB.distance or DEFAULT_DISTANCE)
+    distance.updating = nil
+
+    maxHeight.updating = true
+    maxHeight:SetValue(InteractDB.maxHeight or 0)
+    maxHeight.updating = nil
+
+    autoloot:SetChecked(InteractDB.autoloot == 1)
+    trace:SetChecked(InteractDB.trace)
+    priority:SetText(InteractDB.priority or DEFAULT_PRIORITY)
+    blacklist:SetText(InteractDB.blacklist or DEFAULT_BLACKLIST)
+end
+
+function InteractOptions_Toggle()
+    if not InteractNearest then
+        StaticPopup_Show('INTERACT_WARNING')
+        return
+    end
+
+    if frame:IsVisible() then
+        frame:Hide()
+    else
+        InteractOptions_Refresh()
+        frame:Show()
+    end
+end
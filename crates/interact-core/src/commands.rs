@@ -0,0 +1,429 @@
+//! `/interact` slash command parsing
+//!
+//! The AddOn forwards the text after `/interact` to `InteractCommand(msg)`.
+//! Parsing lives here, with no game access, so every subcommand can be
+//! tested. `run` carries out the commands that only change settings and
+//! hands the rest to the DLL.
+
+use crate::config::{self, Tier, DEFAULT_PRIORITY};
+use crate::game;
+use crate::selection::{RangeConfig, RangeModel};
+use crate::settings::{PRIORITY, RANGE};
+use std::sync::PoisonError;
+
+/// Help text, one line per subcommand
+pub const HELP: &[&str] = &[
+    "/interact status - Show the current settings",
+    "/interact range <yards> [maxHeight] - Set the range (maxHeight 0 = 3D)",
+    "/interact priority [tiers|default] - Show or set the tier order",
+    "/interact blacklist [add|remove <id>|reset] - Show or edit the blacklist",
+    "/interact debug on|off - Toggle trace logging",
+    "/interact dump - List objects in range",
+    "/interact snapshot - Save the visible objects for a bug report",
+    "/interact reload - Re-read interact.toml",
+];
+
+/// A parsed `/interact` command
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    Status,
+    Range {
+        distance: f32,
+        max_height: Option<f32>,
+    },
+    /// `None` shows the current order
+    Priority(Option<Vec<Tier>>),
+    PriorityDefault,
+    BlacklistShow,
+    BlacklistAdd(u32),
+    BlacklistRemove(u32),
+    BlacklistReset,
+    Debug(bool),
+    Dump,
+    Snapshot,
+    Reload,
+}
+
+impl Command {
+    /// Parse the text typed after `/interact`. Words are case-insensitive and
+    /// an empty line shows the help.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut words = input.split_whitespace();
+        let Some(name) = words.next() else {
+            return Ok(Self::Help);
+        };
+        let args: Vec<&str> = words.collect();
+
+        match name.to_ascii_lowercase().as_str() {
+            "help" | "?" => Ok(Self::Help),
+            "status" => no_args(&args, Self::Status),
+            "range" => parse_range(&args),
+            "priority" => parse_priority(&args),
+            "blacklist" => parse_blacklist(&args),
+            "debug" => match args.as_slice() {
+                [state] => parse_switch(state).map(Self::Debug),
+                _ => Err("Usage: /interact debug on|off".to_owned()),
+            },
+            "dump" => no_args(&args, Self::Dump),
+            "snapshot" => no_args(&args, Self::Snapshot),
+            "reload" => no_args(&args, Self::Reload),
+            other => Err(format!(
+                "Unknown command '{other}', type /interact help for a list"
+            )),
+        }
+    }
+}
+
+/// Accept a command only if it has no arguments
+fn no_args(args: &[&str], command: Command) -> Result<Command, String> {
+    if args.is_empty() {
+        Ok(command)
+    } else {
+        Err(format!("'{}' takes no arguments", args.join(" ")))
+    }
+}
+
+fn parse_range(args: &[&str]) -> Result<Command, String> {
+    const USAGE: &str = "Usage: /interact range <yards> [maxHeight]";
+    let (distance, max_height) = match args {
+        [distance] => (*distance, None),
+        [distance, max_height] => (*distance, Some(*max_height)),
+        _ => return Err(USAGE.to_owned()),
+    };
+
+    let distance = parse_yards(distance).filter(|d| *d > 0.0).ok_or(USAGE)?;
+    let max_height = match max_height {
+        Some(text) => Some(parse_yards(text).ok_or(USAGE)?),
+        None => None,
+    };
+    Ok(Command::Range {
+        distance,
+        max_height,
+    })
+}
+
+/// Parse a finite, non-negative number of yards
+fn parse_yards(text: &str) -> Option<f32> {
+    text.parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+}
+
+fn parse_priority(args: &[&str]) -> Result<Command, String> {
+    match args {
+        [] => Ok(Command::Priority(None)),
+        [word] if word.eq_ignore_ascii_case("default") => Ok(Command::PriorityDefault),
+        // Tiers may be separated by spaces, commas or both
+        _ => config::parse_priority(&args.join(","))
+            .map(|order| Command::Priority(Some(order)))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn parse_blacklist(args: &[&str]) -> Result<Command, String> {
+    const USAGE: &str = "Usage: /interact blacklist [add|remove <id>|reset]";
+    let action = args.first().map(|word| word.to_ascii_lowercase());
+    match (action.as_deref(), &args[args.len().min(1)..]) {
+        (None | Some("list"), []) => Ok(Command::BlacklistShow),
+        (Some("reset"), []) => Ok(Command::BlacklistReset),
+        (Some("add"), [id]) => parse_id(id).map(Command::BlacklistAdd),
+        (Some("remove"), [id]) => parse_id(id).map(Command::BlacklistRemove),
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn parse_id(text: &str) -> Result<u32, String> {
+    text.parse()
+        .map_err(|_| format!("'{text}' is not a game object entry ID"))
+}
+
+fn parse_switch(text: &str) -> Result<bool, String> {
+    match text.to_ascii_lowercase().as_str() {
+        "on" | "1" | "true" => Ok(true),
+        "off" | "0" | "false" => Ok(false),
+        _ => Err(format!("Expected on or off, got '{text}'")),
+    }
+}
+
+// =============================================================================
+// Running
+// =============================================================================
+
+/// Run a command and return its output. Commands that need the client
+/// (status, dump, snapshot and reload) are passed to `client`.
+pub fn run(command: Command, client: impl FnOnce(Command) -> Vec<String>) -> Vec<String> {
+    match command {
+        Command::Help => HELP.iter().map(|line| (*line).to_owned()).collect(),
+        Command::Range {
+            distance,
+            max_height,
+        } => {
+            let mut range = RANGE.write().unwrap_or_else(PoisonError::into_inner);
+            range.max_distance = distance;
+            if let Some(max_height) = max_height {
+                range.model = if max_height > 0.0 {
+                    RangeModel::Cylindrical { max_height }
+                } else {
+                    RangeModel::Spherical
+                };
+            }
+            vec![describe_range(&range)]
+        }
+        Command::Priority(order) => {
+            let mut priority = PRIORITY.write().unwrap_or_else(PoisonError::into_inner);
+            if let Some(order) = order {
+                *priority = order;
+            }
+            vec![describe_priority(&priority)]
+        }
+        Command::PriorityDefault => {
+            let mut priority = PRIORITY.write().unwrap_or_else(PoisonError::into_inner);
+            *priority = DEFAULT_PRIORITY.to_vec();
+            vec![describe_priority(&priority)]
+        }
+        Command::BlacklistShow => vec![describe_blacklist()],
+        Command::BlacklistAdd(id) => {
+            let mut ids = game::blacklist();
+            ids.push(id);
+            game::set_blacklist(&ids);
+            vec![format!("Added {id}. {}", describe_blacklist())]
+        }
+        Command::BlacklistRemove(id) => {
+            let ids = game::blacklist();
+            if !ids.contains(&id) {
+                return vec![format!("{id} is not blacklisted")];
+            }
+            let ids: Vec<u32> = ids.into_iter().filter(|entry| *entry != id).collect();
+            game::set_blacklist(&ids);
+            vec![format!("Removed {id}. {}", describe_blacklist())]
+        }
+        Command::BlacklistReset => {
+            game::set_blacklist(config::DEFAULT_BLACKLIST);
+            vec![describe_blacklist()]
+        }
+        Command::Debug(enabled) => {
+            crate::log::set_trace(enabled);
+            vec![format!("Trace logging {}", on_off(enabled))]
+        }
+        Command::Status | Command::Dump | Command::Snapshot | Command::Reload => client(command),
+    }
+}
+
+pub fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+pub fn describe_range(range: &RangeConfig) -> String {
+    let model = match range.model {
+        RangeModel::Spherical => "3D".to_owned(),
+        RangeModel::Cylindrical { max_height } => {
+            format!("horizontal, height limit {max_height:.1} yd")
+        }
+    };
+    let radius = if range.unit_radius > 0.0 || range.gameobject_radius > 0.0 {
+        format!(
+            ", radius {:.1} yd units / {:.1} yd objects",
+            range.unit_radius, range.gameobject_radius
+        )
+    } else {
+        String::new()
+    };
+    format!("Range: {:.1} yd ({model}){radius}", range.max_distance)
+}
+
+pub fn describe_priority(priority: &[Tier]) -> String {
+    let names: Vec<&str> = priority.iter().map(|tier| tier.name()).collect();
+    format!("Priority: {}", names.join(", "))
+}
+
+pub fn describe_blacklist() -> String {
+    let ids: Vec<String> = game::blacklist().iter().map(u32::to_string).collect();
+    if ids.is_empty() {
+        "Blacklist: empty".to_owned()
+    } else {
+        format!("Blacklist: {}", ids.join(", "))
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // General tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_empty_input_shows_help() {
+        assert_eq!(Command::parse(""), Ok(Command::Help));
+        assert_eq!(Command::parse("   "), Ok(Command::Help));
+        assert_eq!(Command::parse("help"), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_commands_are_case_insensitive() {
+        assert_eq!(Command::parse("STATUS"), Ok(Command::Status));
+        assert_eq!(Command::parse("  Dump "), Ok(Command::Dump));
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let err = Command::parse("teleport").unwrap_err();
+        assert!(err.contains("'teleport'"));
+    }
+
+    #[test]
+    fn test_commands_without_arguments_reject_extra_words() {
+        assert!(Command::parse("status now").is_err());
+        assert!(Command::parse("dump all").is_err());
+        assert!(Command::parse("snapshot now").is_err());
+        assert_eq!(Command::parse("snapshot"), Ok(Command::Snapshot));
+        assert_eq!(Command::parse("reload"), Ok(Command::Reload));
+    }
+
+    // -------------------------------------------------------------------------
+    // Range tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_range_distance_only() {
+        assert_eq!(
+            Command::parse("range 6"),
+            Ok(Command::Range {
+                distance: 6.0,
+                max_height: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_with_max_height() {
+        assert_eq!(
+            Command::parse("range 5.5 3"),
+            Ok(Command::Range {
+                distance: 5.5,
+                max_height: Some(3.0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_rejects_bad_values() {
+        assert!(Command::parse("range").is_err());
+        assert!(Command::parse("range far").is_err());
+        assert!(Command::parse("range 0").is_err());
+        assert!(Command::parse("range -2").is_err());
+        assert!(Command::parse("range 5 -1").is_err());
+        assert!(Command::parse("range inf").is_err());
+        assert!(Command::parse("range 5 3 1").is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Priority tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_priority_show_and_default() {
+        assert_eq!(Command::parse("priority"), Ok(Command::Priority(None)));
+        assert_eq!(
+            Command::parse("priority Default"),
+            Ok(Command::PriorityDefault)
+        );
+    }
+
+    #[test]
+    fn test_priority_accepts_spaces_and_commas() {
+        let expected = Ok(Command::Priority(Some(vec![
+            Tier::GameObject,
+            Tier::Lootable,
+            Tier::Alive,
+        ])));
+        assert_eq!(
+            Command::parse("priority gameobject lootable alive"),
+            expected
+        );
+        assert_eq!(
+            Command::parse("priority gameobject,lootable, alive"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_priority_rejects_unknown_tier() {
+        let err = Command::parse("priority lootable mailbox").unwrap_err();
+        assert!(err.contains("mailbox"));
+    }
+
+    // -------------------------------------------------------------------------
+    // Blacklist tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_blacklist_subcommands() {
+        assert_eq!(Command::parse("blacklist"), Ok(Command::BlacklistShow));
+        assert_eq!(Command::parse("blacklist list"), Ok(Command::BlacklistShow));
+        assert_eq!(
+            Command::parse("blacklist add 12345"),
+            Ok(Command::BlacklistAdd(12345))
+        );
+        assert_eq!(
+            Command::parse("blacklist REMOVE 12345"),
+            Ok(Command::BlacklistRemove(12345))
+        );
+        assert_eq!(
+            Command::parse("blacklist reset"),
+            Ok(Command::BlacklistReset)
+        );
+    }
+
+    #[test]
+    fn test_blacklist_rejects_bad_arguments() {
+        assert!(Command::parse("blacklist add").is_err());
+        assert!(Command::parse("blacklist add chest").is_err());
+        assert!(Command::parse("blacklist add 1 2").is_err());
+        assert!(Command::parse("blacklist clear").is_err());
+        assert!(Command::parse("blacklist reset now").is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Debug tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_debug_switch() {
+        assert_eq!(Command::parse("debug on"), Ok(Command::Debug(true)));
+        assert_eq!(Command::parse("debug OFF"), Ok(Command::Debug(false)));
+        assert_eq!(Command::parse("debug 1"), Ok(Command::Debug(true)));
+        assert!(Command::parse("debug").is_err());
+        assert!(Command::parse("debug maybe").is_err());
+    }
+
+    #[test]
+    fn test_help_lists_every_command() {
+        for name in [
+            "status",
+            "range",
+            "priority",
+            "blacklist",
+            "debug",
+            "dump",
+            "snapshot",
+            "reload",
+        ] {
+            assert!(
+                HELP.iter()
+                    .any(|line| line.starts_with(&format!("/interact {name}"))),
+                "{name} missing from help"
+            );
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! Configuration file for interact-rs
+//!
+//! Settings are read from `interact.toml` in the WoW directory when the
+//! client starts, and again whenever `InteractReloadConfig()` is called.
+//! Every key is optional; a missing file or key keeps the built-in default,
+//! which matches the behaviour of the DLL without a config file.
+//!
+//! Example:
+//!
+//! ```toml
+//! priority = ["lootable", "gameobject", "skinnable", "alive", "service"]
+//!
+//! [range]
+//! distance = 5.0
+//! max_height = 3.0
+//!
+//! [object_radius]
+//! 179830 = 2.0
+//!
+//! [facing]
+//! cone = 120.0
+//!
+//! [filters]
+//! exclude = "Dusty*"
+//!
+//! [blacklist]
+//! objects = [179830, 179831, 179785, 179786]
+//!
+//! [log]
+//! trace = false
+//! ```
+
+use crate::errors::ConfigError;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Config file name, relative to the WoW directory
+pub const CONFIG_FILE: &str = "interact.toml";
+
+/// Game objects that are never selected unless the config says otherwise
+pub const DEFAULT_BLACKLIST: &[u32] = &[179830, 179831, 179785, 179786];
+
+/// Candidate tiers in the order they are selected by default
+pub const DEFAULT_PRIORITY: [Tier; Tier::COUNT] = [
+    Tier::Lootable,
+    Tier::GameObject,
+    Tier::Skinnable,
+    Tier::Alive,
+    Tier::Service,
+];
+
+// =============================================================================
+// Tiers
+// =============================================================================
+
+/// Category a candidate falls into; the nearest object of the first
+/// non-empty tier in the priority order is selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    /// Dead units with loot
+    Lootable = 0,
+    /// Chests, herbs, mining nodes and other usable objects
+    GameObject = 1,
+    /// Dead units that can be skinned but not looted
+    Skinnable = 2,
+    /// Living, selectable units
+    Alive = 3,
+    /// Mailboxes, auction boards, meeting stones
+    Service = 4,
+}
+
+impl Tier {
+    /// Number of tiers
+    pub const COUNT: usize = 5;
+
+    /// Look up a tier by its config name, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        DEFAULT_PRIORITY
+            .into_iter()
+            .find(|tier| tier.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Name used in the config file and by `InteractSetPriority`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Lootable => "lootable",
+            Self::GameObject => "gameobject",
+            Self::Skinnable => "skinnable",
+            Self::Alive => "alive",
+            Self::Service => "service",
+        }
+    }
+}
+
+// =============================================================================
+// Lists
+// =============================================================================
+
+/// Parse a comma-separated priority order such as `"lootable, alive"`
+pub fn parse_priority(text: &str) -> Result<Vec<Tier>, ConfigError> {
+    let priority = split_list(text)
+        .map(|name| {
+            Tier::from_name(name)
+                .ok_or_else(|| invalid("priority", format!("unknown tier '{name}'")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    validate_priority(&priority)?;
+    Ok(priority)
+}
+
+/// Parse a comma-separated list of game object entry IDs
+pub fn parse_id_list(text: &str) -> Result<Vec<u32>, ConfigError> {
+    split_list(text)
+        .map(|id| {
+            id.parse()
+                .map_err(|_| invalid("blacklist", format!("'{id}' is not an entry ID")))
+        })
+        .collect()
+}
+
+/// Split a comma-separated list, trimming entries and dropping empty ones
+fn split_list(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Reject tiers listed more than once
+fn validate_priority(priority: &[Tier]) -> Result<(), ConfigError> {
+    for (i, tier) in priority.iter().enumerate() {
+        if priority[..i].contains(tier) {
+            return Err(invalid(
+                "priority",
+                format!("{} is listed twice", tier.name()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Config
+// =============================================================================
+
+/// Contents of `interact.toml`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Tiers in selection order; tiers left out are never selected
+    pub priority: Vec<Tier>,
+    pub range: RangeSection,
+    /// Bounding radius in yards per game object entry ID
+    pub object_radius: BTreeMap<String, f32>,
+    pub facing: FacingSection,
+    pub line_of_sight: LineOfSightSection,
+    pub filters: FiltersSection,
+    pub blacklist: BlacklistSection,
+    pub log: LogSection,
+}
+
+/// `[range]` - see `InteractSetRange`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RangeSection {
+    pub distance: f32,
+    /// Maximum height difference; 0 measures full 3D distance
+    pub max_height: f32,
+    pub unit_radius: f32,
+    pub object_radius: f32,
+}
+
+/// `[facing]` - see `InteractSetFacing`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FacingSection {
+    /// Full cone width in degrees; 0 or 360 disables the cone
+    pub cone: f32,
+    pub weight: f32,
+}
+
+/// `[line_of_sight]` - see `InteractSetLineOfSight`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LineOfSightSection {
+    pub enabled: bool,
+    pub max_checks: u32,
+}
+
+/// `[filters]` - see `InteractSetNameFilters`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FiltersSection {
+    pub include: String,
+    pub exclude: String,
+}
+
+/// `[blacklist]`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlacklistSection {
+    /// Game object entry IDs that are never selected
+    pub objects: Vec<u32>,
+}
+
+/// `[log]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogSection {
+    /// Write verbose trace messages, see `InteractSetTrace`
+    pub trace: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            priority: DEFAULT_PRIORITY.to_vec(),
+            range: RangeSection::default(),
+            object_radius: BTreeMap::new(),
+            facing: FacingSection::default(),
+            line_of_sight: LineOfSightSection::default(),
+            filters: FiltersSection::default(),
+            blacklist: BlacklistSection::default(),
+            log: LogSection::default(),
+        }
+    }
+}
+
+impl Default for RangeSection {
+    fn default() -> Self {
+        Self {
+            distance: 5.0,
+            max_height: 0.0,
+            unit_radius: 0.0,
+            object_radius: 0.0,
+        }
+    }
+}
+
+impl Default for LineOfSightSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_checks: 3,
+        }
+    }
+}
+
+impl Default for BlacklistSection {
+    fn default() -> Self {
+        Self {
+            objects: DEFAULT_BLACKLIST.to_vec(),
+        }
+    }
+}
+
+impl Config {
+    /// Parse and validate config file contents
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| ConfigError::Parse(e.message().to_owned()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load a config file, falling back to the defaults if it does not exist
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::Io(e)),
+        }
+    }
+
+    /// Check values that parse but make no sense
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        validate_priority(&self.priority)?;
+
+        if !(self.range.distance.is_finite() && self.range.distance > 0.0) {
+            return Err(invalid("range.distance", "must be greater than 0"));
+        }
+        non_negative("range.max_height", self.range.max_height)?;
+        non_negative("range.unit_radius", self.range.unit_radius)?;
+        non_negative("range.object_radius", self.range.object_radius)?;
+
+        for (entry, radius) in &self.object_radius {
+            if entry.parse::<u32>().is_err() {
+                return Err(invalid(
+                    "object_radius",
+                    format!("'{entry}' is not an entry ID"),
+                ));
+            }
+            non_negative("object_radius", *radius)?;
+        }
+
+        if !(0.0..=360.0).contains(&self.facing.cone) {
+            return Err(invalid("facing.cone", "must be between 0 and 360"));
+        }
+        non_negative("facing.weight", self.facing.weight)?;
+
+        if self.line_of_sight.max_checks == 0 {
+            return Err(invalid("line_of_sight.max_checks", "must be at least 1"));
+        }
+
+        Ok(())
+    }
+
+    /// Per-entry radii with their keys parsed. Only valid after `validate`.
+    pub fn object_radii(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        self.object_radius
+            .iter()
+            .filter_map(|(entry, radius)| Some((entry.parse().ok()?, *radius)))
+    }
+}
+
+/// Build a validation error for `key`
+fn invalid(key: &'static str, message: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid {
+        key,
+        message: message.into(),
+    }
+}
+
+/// Reject negative and non-finite values
+fn non_negative(key: &'static str, value: f32) -> Result<(), ConfigError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(key, "must be 0 or greater"))
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)] // Exact float comparisons are intentional in these tests
+
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // Default tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_empty_file_uses_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_defaults_match_builtin_behaviour() {
+        let config = Config::default();
+        assert_eq!(config.priority, DEFAULT_PRIORITY);
+        assert_eq!(config.range.distance, 5.0);
+        assert_eq!(config.range.max_height, 0.0);
+        assert_eq!(config.facing.cone, 0.0);
+        assert_eq!(config.facing.weight, 0.0);
+        assert!(!config.line_of_sight.enabled);
+        assert_eq!(config.line_of_sight.max_checks, 3);
+        assert!(config.filters.include.is_empty());
+        assert!(config.filters.exclude.is_empty());
+        assert_eq!(config.blacklist.objects, DEFAULT_BLACKLIST);
+        assert!(!config.log.trace);
+    }
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let path = Path::new("this-file-does-not-exist/interact.toml");
+        assert_eq!(Config::load(path).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_tier_indices_are_dense() {
+        for (i, tier) in DEFAULT_PRIORITY.iter().enumerate() {
+            assert_eq!(*tier as usize, i);
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Parsing tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = Config::parse(
+            r#"
+            priority = ["gameobject", "lootable"]
+
+            [range]
+            distance = 6.5
+            max_height = 3
+            unit_radius = 0.5
+            object_radius = 1.0
+
+            [object_radius]
+            179830 = 2.0
+
+            [facing]
+            cone = 120
+            weight = 2
+
+            [line_of_sight]
+            enabled = true
+            max_checks = 5
+
+            [filters]
+            include = "*Vein,*Deposit"
+            exclude = "Dusty*"
+
+            [blacklist]
+            objects = [1, 2]
+
+            [log]
+            trace = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.priority, [Tier::GameObject, Tier::Lootable]);
+        assert_eq!(config.range.distance, 6.5);
+        assert_eq!(config.range.max_height, 3.0);
+        assert_eq!(config.range.unit_radius, 0.5);
+        assert_eq!(config.range.object_radius, 1.0);
+        assert_eq!(config.object_radii().collect::<Vec<_>>(), [(179830, 2.0)]);
+        assert_eq!(config.facing.cone, 120.0);
+        assert_eq!(config.facing.weight, 2.0);
+        assert!(config.line_of_sight.enabled);
+        assert_eq!(config.line_of_sight.max_checks, 5);
+        assert_eq!(config.filters.include, "*Vein,*Deposit");
+        assert_eq!(config.filters.exclude, "Dusty*");
+        assert_eq!(config.blacklist.objects, [1, 2]);
+        assert!(config.log.trace);
+    }
+
+    #[test]
+    fn test_parse_partial_section_keeps_other_defaults() {
+        let config = Config::parse("[range]\nmax_height = 3.0\n").unwrap();
+        assert_eq!(config.range.distance, 5.0);
+        assert_eq!(config.range.max_height, 3.0);
+        assert_eq!(config.blacklist.objects, DEFAULT_BLACKLIST);
+    }
+
+    #[test]
+    fn test_parse_empty_blacklist() {
+        let config = Config::parse("[blacklist]\nobjects = []\n").unwrap();
+        assert!(config.blacklist.objects.is_empty());
+    }
+
+    #[test]
+    fn test_parse_syntax_error() {
+        let err = Config::parse("[range\ndistance = 5").unwrap_err();
+        assert!(matches!(err, ConfigError::Parse(_)));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        assert!(matches!(
+            Config::parse("[range]\ndistanse = 5.0\n"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            Config::parse("[ranges]\n"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_tier() {
+        assert!(matches!(
+            Config::parse("priority = [\"lootable\", \"mailbox\"]\n"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_wrong_type() {
+        assert!(matches!(
+            Config::parse("[line_of_sight]\nenabled = \"yes\"\n"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_tier_names_match_config_file() {
+        for tier in DEFAULT_PRIORITY {
+            let config = Config::parse(&format!("priority = [\"{}\"]", tier.name())).unwrap();
+            assert_eq!(config.priority, [tier]);
+            assert_eq!(Tier::from_name(tier.name()), Some(tier));
+        }
+        assert_eq!(Tier::from_name("GameObject"), Some(Tier::GameObject));
+        assert_eq!(Tier::from_name("mailbox"), None);
+    }
+
+    // -------------------------------------------------------------------------
+    // List tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_priority_list() {
+        assert_eq!(
+            parse_priority("alive, Lootable ,service").unwrap(),
+            [Tier::Alive, Tier::Lootable, Tier::Service]
+        );
+        assert!(parse_priority("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_priority_rejects_unknown_and_duplicate() {
+        let err = parse_priority("lootable,mailbox").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value for priority: unknown tier 'mailbox'"
+        );
+        let err = parse_priority("alive,lootable,alive").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value for priority: alive is listed twice"
+        );
+    }
+
+    #[test]
+    fn test_parse_id_list() {
+        assert_eq!(parse_id_list("179830, 179831,,").unwrap(), [179830, 179831]);
+        assert!(parse_id_list(" ").unwrap().is_empty());
+        assert!(matches!(
+            parse_id_list("179830,chest"),
+            Err(ConfigError::Invalid {
+                key: "blacklist",
+                ..
+            })
+        ));
+        assert!(parse_id_list("-1").is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Validation tests
+    // -------------------------------------------------------------------------
+
+    fn invalid_key(text: &str) -> &'static str {
+        match Config::parse(text) {
+            Err(ConfigError::Invalid { key, .. }) => key,
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_duplicate_tier() {
+        assert_eq!(
+            invalid_key("priority = [\"alive\", \"service\", \"alive\"]\n"),
+            "priority"
+        );
+    }
+
+    #[test]
+    fn test_validate_range() {
+        assert_eq!(invalid_key("[range]\ndistance = 0\n"), "range.distance");
+        assert_eq!(invalid_key("[range]\ndistance = -5\n"), "range.distance");
+        assert_eq!(invalid_key("[range]\ndistance = nan\n"), "range.distance");
+        assert_eq!(
+            invalid_key("[range]\nmax_height = -1\n"),
+            "range.max_height"
+        );
+        assert_eq!(
+            invalid_key("[range]\nunit_radius = -1\n"),
+            "range.unit_radius"
+        );
+        assert_eq!(
+            invalid_key("[range]\nobject_radius = inf\n"),
+            "range.object_radius"
+        );
+    }
+
+    #[test]
+    fn test_validate_object_radius() {
+        assert_eq!(
+            invalid_key("[object_radius]\nmailbox = 2.0\n"),
+            "object_radius"
+        );
+        assert_eq!(invalid_key("[object_radius]\n1 = -2.0\n"), "object_radius");
+    }
+
+    #[test]
+    fn test_validate_facing() {
+        assert_eq!(invalid_key("[facing]\ncone = 400\n"), "facing.cone");
+        assert_eq!(invalid_key("[facing]\ncone = -1\n"), "facing.cone");
+        assert_eq!(invalid_key("[facing]\nweight = -1\n"), "facing.weight");
+    }
+
+    #[test]
+    fn test_validate_line_of_sight() {
+        assert_eq!(
+            invalid_key("[line_of_sight]\nmax_checks = 0\n"),
+            "line_of_sight.max_checks"
+        );
+    }
+
+    #[test]
+    fn test_validation_error_message_names_key() {
+        let err = Config::parse("[facing]\ncone = 400\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value for facing.cone: must be between 0 and 360"
+        );
+    }
+}
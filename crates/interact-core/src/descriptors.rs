@@ -0,0 +1,545 @@
+//! Descriptor (update field) models for WoW 1.12.1 objects
+//!
+//! Plain data read once from an object's descriptor so that selection rules
+//! can work on typed fields instead of raw offsets. Reading lives in
+//! `game.rs`; this module only decodes values and has no memory access.
+//!
+//! Descriptor offsets are the 1.12.1 update field indices multiplied by 4:
+//!
+//! | Field                        | Offset  |
+//! |------------------------------|---------|
+//! | `UNIT_FIELD_SUMMONEDBY`      | `0x30`  |
+//! | `UNIT_FIELD_TARGET`          | `0x40`  |
+//! | `UNIT_FIELD_HEALTH`          | `0x58`  |
+//! | `UNIT_FIELD_MAXHEALTH`       | `0x70`  |
+//! | `UNIT_FIELD_LEVEL`           | `0x88`  |
+//! | `UNIT_FIELD_FACTIONTEMPLATE` | `0x8C`  |
+//! | `UNIT_FIELD_FLAGS`           | `0xB8`  |
+//! | `UNIT_DYNAMIC_FLAGS`         | `0x23C` |
+//! | `UNIT_NPC_FLAGS`             | `0x24C` |
+//! | `GAMEOBJECT_CREATED_BY`      | `0x18`  |
+//! | `GAMEOBJECT_DISPLAYID`       | `0x20`  |
+//! | `GAMEOBJECT_FLAGS`           | `0x24`  |
+//! | `GAMEOBJECT_STATE`           | `0x38`  |
+//! | `GAMEOBJECT_TYPE_ID`         | `0x54`  |
+
+// =============================================================================
+// Unit Flags
+// =============================================================================
+
+/// `UNIT_FIELD_FLAGS` bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnitFlags(pub u32);
+
+impl UnitFlags {
+    /// Unit cannot be attacked
+    pub const NON_ATTACKABLE: Self = Self(0x0000_0002);
+    /// Unit is flagged for PvP
+    pub const PVP: Self = Self(0x0000_1000);
+    /// Unit is in combat
+    pub const IN_COMBAT: Self = Self(0x0008_0000);
+    /// Unit is on a flight path
+    pub const TAXI_FLIGHT: Self = Self(0x0010_0000);
+    /// Unit cannot be selected
+    pub const NOT_SELECTABLE: Self = Self(0x0200_0000);
+    /// Unit corpse can be skinned
+    pub const SKINNABLE: Self = Self(0x0400_0000);
+
+    /// Check if all bits of `flag` are set
+    #[inline]
+    pub fn contains(self, flag: Self) -> bool {
+        (self.0 & flag.0) == flag.0
+    }
+}
+
+/// `UNIT_DYNAMIC_FLAGS` bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DynamicFlags(pub u32);
+
+impl DynamicFlags {
+    /// Corpse has loot for the player
+    pub const LOOTABLE: Self = Self(0x0001);
+    /// Unit is tracked on the minimap
+    pub const TRACK_UNIT: Self = Self(0x0002);
+    /// Unit is tapped by another player or group
+    pub const TAPPED: Self = Self(0x0004);
+    /// Unit is dead
+    pub const DEAD: Self = Self(0x0020);
+
+    /// Check if all bits of `flag` are set
+    #[inline]
+    pub fn contains(self, flag: Self) -> bool {
+        (self.0 & flag.0) == flag.0
+    }
+}
+
+/// `UNIT_NPC_FLAGS` bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NpcFlags(pub u32);
+
+impl NpcFlags {
+    pub const GOSSIP: Self = Self(0x0001);
+    pub const QUEST_GIVER: Self = Self(0x0002);
+    pub const VENDOR: Self = Self(0x0004);
+    pub const FLIGHT_MASTER: Self = Self(0x0008);
+    pub const TRAINER: Self = Self(0x0010);
+    pub const SPIRIT_HEALER: Self = Self(0x0020);
+    pub const SPIRIT_GUIDE: Self = Self(0x0040);
+    pub const INNKEEPER: Self = Self(0x0080);
+    pub const BANKER: Self = Self(0x0100);
+    pub const PETITIONER: Self = Self(0x0200);
+    pub const TABARD_DESIGNER: Self = Self(0x0400);
+    pub const BATTLEMASTER: Self = Self(0x0800);
+    pub const AUCTIONEER: Self = Self(0x1000);
+    pub const STABLE_MASTER: Self = Self(0x2000);
+    pub const REPAIR: Self = Self(0x4000);
+
+    /// Check if all bits of `flag` are set
+    #[inline]
+    pub fn contains(self, flag: Self) -> bool {
+        (self.0 & flag.0) == flag.0
+    }
+
+    /// Whether the NPC offers any service (gossip, vendor, trainer, ...)
+    #[inline]
+    pub fn is_service(self) -> bool {
+        self.0 != 0
+    }
+}
+
+// =============================================================================
+// Creature Type
+// =============================================================================
+
+/// Creature type from the creature cache
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreatureType {
+    #[default]
+    None = 0,
+    Beast = 1,
+    Dragonkin = 2,
+    Demon = 3,
+    Elemental = 4,
+    Giant = 5,
+    Undead = 6,
+    Humanoid = 7,
+    Critter = 8,
+    Mechanical = 9,
+    NotSpecified = 10,
+    Totem = 11,
+}
+
+impl From<u32> for CreatureType {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => Self::Beast,
+            2 => Self::Dragonkin,
+            3 => Self::Demon,
+            4 => Self::Elemental,
+            5 => Self::Giant,
+            6 => Self::Undead,
+            7 => Self::Humanoid,
+            8 => Self::Critter,
+            9 => Self::Mechanical,
+            10 => Self::NotSpecified,
+            11 => Self::Totem,
+            _ => Self::None,
+        }
+    }
+}
+
+// =============================================================================
+// Unit Descriptor
+// =============================================================================
+
+/// Typed snapshot of a unit's descriptor fields.
+///
+/// `creature_type` and `creature_family` come from the creature cache rather
+/// than the descriptor; they are `None`/0 for players and uncached creatures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnitDescriptor {
+    pub summoned_by: u64,
+    pub target: u64,
+    pub health: i32,
+    pub max_health: u32,
+    pub level: u32,
+    pub faction_template: u32,
+    pub flags: UnitFlags,
+    pub dynamic_flags: DynamicFlags,
+    pub npc_flags: NpcFlags,
+    pub creature_type: CreatureType,
+    pub creature_family: u32,
+}
+
+impl UnitDescriptor {
+    /// Whether the unit is dead (health is zero)
+    #[inline]
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Whether the corpse has loot for the player
+    #[inline]
+    pub fn is_lootable(&self) -> bool {
+        self.dynamic_flags.contains(DynamicFlags::LOOTABLE)
+    }
+
+    /// Whether the corpse can be skinned
+    #[inline]
+    pub fn is_skinnable(&self) -> bool {
+        self.flags.contains(UnitFlags::SKINNABLE)
+    }
+
+    /// Whether the unit can be selected at all
+    #[inline]
+    pub fn is_selectable(&self) -> bool {
+        !self.flags.contains(UnitFlags::NOT_SELECTABLE)
+    }
+}
+
+// =============================================================================
+// Game Object Type
+// =============================================================================
+
+/// Game object type (`GAMEOBJECT_TYPE_ID`)
+///
+/// Herbs and mining veins are `Chest` objects locked behind a gathering skill.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameObjectType {
+    Door = 0,
+    Button = 1,
+    QuestGiver = 2,
+    Chest = 3,
+    Binder = 4,
+    Generic = 5,
+    Trap = 6,
+    Chair = 7,
+    SpellFocus = 8,
+    Text = 9,
+    Goober = 10,
+    Transport = 11,
+    AreaDamage = 12,
+    Camera = 13,
+    MapObject = 14,
+    MoTransport = 15,
+    DuelArbiter = 16,
+    FishingNode = 17,
+    Ritual = 18,
+    Mailbox = 19,
+    AuctionHouse = 20,
+    GuardPost = 21,
+    SpellCaster = 22,
+    MeetingStone = 23,
+    FlagStand = 24,
+    FishingHole = 25,
+    FlagDrop = 26,
+    #[default]
+    Unknown = u32::MAX,
+}
+
+impl From<u32> for GameObjectType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Door,
+            1 => Self::Button,
+            2 => Self::QuestGiver,
+            3 => Self::Chest,
+            4 => Self::Binder,
+            5 => Self::Generic,
+            6 => Self::Trap,
+            7 => Self::Chair,
+            8 => Self::SpellFocus,
+            9 => Self::Text,
+            10 => Self::Goober,
+            11 => Self::Transport,
+            12 => Self::AreaDamage,
+            13 => Self::Camera,
+            14 => Self::MapObject,
+            15 => Self::MoTransport,
+            16 => Self::DuelArbiter,
+            17 => Self::FishingNode,
+            18 => Self::Ritual,
+            19 => Self::Mailbox,
+            20 => Self::AuctionHouse,
+            21 => Self::GuardPost,
+            22 => Self::SpellCaster,
+            23 => Self::MeetingStone,
+            24 => Self::FlagStand,
+            25 => Self::FishingHole,
+            26 => Self::FlagDrop,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl GameObjectType {
+    /// Whether right-clicking this type does something useful.
+    ///
+    /// Doors, traps, chairs, spell foci (anvils, campfires), transports,
+    /// fishing pools and decorative objects are never selected.
+    pub fn is_interactable(self) -> bool {
+        matches!(
+            self,
+            Self::Button
+                | Self::QuestGiver
+                | Self::Chest
+                | Self::Binder
+                | Self::Text
+                | Self::Goober
+                | Self::FishingNode
+                | Self::Ritual
+                | Self::Mailbox
+                | Self::AuctionHouse
+                | Self::SpellCaster
+                | Self::MeetingStone
+                | Self::FlagStand
+                | Self::FlagDrop
+        )
+    }
+
+    /// Whether this type opens a service window rather than yielding loot.
+    ///
+    /// Service objects are selected after everything else so that standing
+    /// next to a mailbox does not steal the keybind from a corpse or NPC.
+    pub fn is_service(self) -> bool {
+        matches!(
+            self,
+            Self::Mailbox | Self::AuctionHouse | Self::Binder | Self::MeetingStone
+        )
+    }
+}
+
+/// `GAMEOBJECT_STATE` value
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameObjectState {
+    /// Used/opened (e.g. an open door or looted chest)
+    Active = 0,
+    /// Default state, ready to be used
+    #[default]
+    Ready = 1,
+    /// Alternative active state
+    ActiveAlternative = 2,
+}
+
+impl From<u32> for GameObjectState {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Active,
+            2 => Self::ActiveAlternative,
+            _ => Self::Ready,
+        }
+    }
+}
+
+/// `GAMEOBJECT_FLAGS` bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameObjectFlags(pub u32);
+
+impl GameObjectFlags {
+    /// Object is being used by someone
+    pub const IN_USE: Self = Self(0x0001);
+    /// Object is locked (requires a key or lockpicking)
+    pub const LOCKED: Self = Self(0x0002);
+    /// Object requires a condition (e.g. a quest) to be usable
+    pub const INTERACT_COND: Self = Self(0x0004);
+    /// Object is a transport
+    pub const TRANSPORT: Self = Self(0x0008);
+    /// Object cannot be interacted with
+    pub const NO_INTERACT: Self = Self(0x0010);
+
+    /// Check if all bits of `flag` are set
+    #[inline]
+    pub fn contains(self, flag: Self) -> bool {
+        (self.0 & flag.0) == flag.0
+    }
+}
+
+// =============================================================================
+// Game Object Descriptor
+// =============================================================================
+
+/// Typed snapshot of a game object's descriptor fields
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameObjectDescriptor {
+    pub created_by: u64,
+    pub display_id: u32,
+    pub flags: GameObjectFlags,
+    pub state: GameObjectState,
+    pub go_type: GameObjectType,
+}
+
+impl GameObjectDescriptor {
+    /// Whether the object can currently be interacted with.
+    ///
+    /// Rejects non-interactable types and objects that are locked, in use
+    /// or flagged as not interactable.
+    pub fn is_interactable(&self) -> bool {
+        self.go_type.is_interactable()
+            && !self.flags.contains(GameObjectFlags::LOCKED)
+            && !self.flags.contains(GameObjectFlags::IN_USE)
+            && !self.flags.contains(GameObjectFlags::NO_INTERACT)
+    }
+
+    /// Whether the object was created by the given player (e.g. their own
+    /// fishing bobber)
+    #[inline]
+    pub fn is_created_by(&self, guid: u64) -> bool {
+        self.created_by != 0 && self.created_by == guid
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_flags_contains() {
+        let flags = UnitFlags(UnitFlags::IN_COMBAT.0 | UnitFlags::SKINNABLE.0);
+        assert!(flags.contains(UnitFlags::IN_COMBAT));
+        assert!(flags.contains(UnitFlags::SKINNABLE));
+        assert!(!flags.contains(UnitFlags::TAXI_FLIGHT));
+        assert!(UnitFlags::default().contains(UnitFlags(0)));
+    }
+
+    #[test]
+    fn test_npc_flags_services() {
+        let flags = NpcFlags(NpcFlags::VENDOR.0 | NpcFlags::REPAIR.0);
+        assert!(flags.contains(NpcFlags::VENDOR));
+        assert!(flags.contains(NpcFlags::REPAIR));
+        assert!(!flags.contains(NpcFlags::QUEST_GIVER));
+        assert!(flags.is_service());
+        assert!(!NpcFlags::default().is_service());
+    }
+
+    #[test]
+    fn test_npc_flags_values_match_client() {
+        assert_eq!(NpcFlags::QUEST_GIVER.0, 0x2);
+        assert_eq!(NpcFlags::FLIGHT_MASTER.0, 0x8);
+        assert_eq!(NpcFlags::BANKER.0, 0x100);
+        assert_eq!(NpcFlags::REPAIR.0, 0x4000);
+    }
+
+    #[test]
+    fn test_creature_type_from_values() {
+        assert_eq!(CreatureType::from(1), CreatureType::Beast);
+        assert_eq!(CreatureType::from(7), CreatureType::Humanoid);
+        assert_eq!(CreatureType::from(11), CreatureType::Totem);
+        assert_eq!(CreatureType::from(0), CreatureType::None);
+        assert_eq!(CreatureType::from(12), CreatureType::None);
+    }
+
+    #[test]
+    fn test_unit_descriptor_corpse_state() {
+        let corpse = UnitDescriptor {
+            health: 0,
+            dynamic_flags: DynamicFlags::LOOTABLE,
+            flags: UnitFlags::SKINNABLE,
+            ..UnitDescriptor::default()
+        };
+        assert!(corpse.is_dead());
+        assert!(corpse.is_lootable());
+        assert!(corpse.is_skinnable());
+
+        let alive = UnitDescriptor {
+            health: 42,
+            ..UnitDescriptor::default()
+        };
+        assert!(!alive.is_dead());
+        assert!(!alive.is_lootable());
+        assert!(!alive.is_skinnable());
+    }
+
+    #[test]
+    fn test_gameobject_type_from_values() {
+        assert_eq!(GameObjectType::from(0), GameObjectType::Door);
+        assert_eq!(GameObjectType::from(3), GameObjectType::Chest);
+        assert_eq!(GameObjectType::from(17), GameObjectType::FishingNode);
+        assert_eq!(GameObjectType::from(19), GameObjectType::Mailbox);
+        assert_eq!(GameObjectType::from(26), GameObjectType::FlagDrop);
+        assert_eq!(GameObjectType::from(27), GameObjectType::Unknown);
+        assert_eq!(GameObjectType::default(), GameObjectType::Unknown);
+    }
+
+    #[test]
+    fn test_gameobject_type_interactable() {
+        assert!(GameObjectType::Chest.is_interactable());
+        assert!(GameObjectType::Goober.is_interactable());
+        assert!(GameObjectType::Mailbox.is_interactable());
+        assert!(!GameObjectType::Door.is_interactable());
+        assert!(!GameObjectType::SpellFocus.is_interactable());
+        assert!(!GameObjectType::FishingHole.is_interactable());
+        assert!(!GameObjectType::Unknown.is_interactable());
+    }
+
+    #[test]
+    fn test_gameobject_type_service() {
+        assert!(GameObjectType::Mailbox.is_service());
+        assert!(GameObjectType::AuctionHouse.is_service());
+        assert!(!GameObjectType::Chest.is_service());
+        assert!(!GameObjectType::QuestGiver.is_service());
+    }
+
+    #[test]
+    fn test_gameobject_state_from_values() {
+        assert_eq!(GameObjectState::from(0), GameObjectState::Active);
+        assert_eq!(GameObjectState::from(1), GameObjectState::Ready);
+        assert_eq!(GameObjectState::from(2), GameObjectState::ActiveAlternative);
+        assert_eq!(GameObjectState::from(99), GameObjectState::Ready);
+    }
+
+    #[test]
+    fn test_gameobject_descriptor_interactable() {
+        let chest = GameObjectDescriptor {
+            go_type: GameObjectType::Chest,
+            ..GameObjectDescriptor::default()
+        };
+        assert!(chest.is_interactable());
+
+        for flag in [
+            GameObjectFlags::LOCKED,
+            GameObjectFlags::IN_USE,
+            GameObjectFlags::NO_INTERACT,
+        ] {
+            let blocked = GameObjectDescriptor {
+                flags: flag,
+                ..chest
+            };
+            assert!(!blocked.is_interactable());
+        }
+
+        let door = GameObjectDescriptor {
+            go_type: GameObjectType::Door,
+            ..GameObjectDescriptor::default()
+        };
+        assert!(!door.is_interactable());
+    }
+
+    #[test]
+    fn test_gameobject_descriptor_created_by() {
+        let bobber = GameObjectDescriptor {
+            created_by: 42,
+            go_type: GameObjectType::FishingNode,
+            ..GameObjectDescriptor::default()
+        };
+        assert!(bobber.is_created_by(42));
+        assert!(!bobber.is_created_by(43));
+        assert!(!GameObjectDescriptor::default().is_created_by(0));
+    }
+
+    #[test]
+    fn test_unit_descriptor_selectable() {
+        let unit = UnitDescriptor {
+            flags: UnitFlags::NOT_SELECTABLE,
+            ..UnitDescriptor::default()
+        };
+        assert!(!unit.is_selectable());
+        assert!(UnitDescriptor::default().is_selectable());
+    }
+}
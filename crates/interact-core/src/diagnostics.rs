@@ -0,0 +1,300 @@
+//! Self-check report for `InteractDiagnostics()`
+//!
+//! The report covers every layer a user's "it doesn't work" could come from -
+//! hooks, Lua, client build, world, object manager and config. The DLL
+//! gathers it from the running client; formatting lives here so it can be
+//! tested without one.
+
+use crate::game::WorldState;
+
+// =============================================================================
+// Report
+// =============================================================================
+
+/// Snapshot of everything `InteractDiagnostics()` reports
+#[derive(Debug, Clone, Default, PartialEq)]
+#[allow(clippy::struct_excessive_bools)] // Independent checks, not a state machine
+pub struct Report {
+    pub version: &'static str,
+    /// `SysMsgInitialize` ran and set up the remaining hooks
+    pub hooks_initialized: bool,
+    pub bootstrap_hook_enabled: bool,
+    pub script_hook_enabled: bool,
+    /// Frame tick hook, which per-frame caching depends on
+    pub frame_hook_enabled: bool,
+    /// Event signal hook, which event handlers depend on
+    pub event_hook_enabled: bool,
+    /// Tasks waiting in the frame tick scheduler
+    pub scheduled_tasks: usize,
+    /// Handlers subscribed to client events
+    pub event_handlers: usize,
+    pub lua_initialized: bool,
+    /// Build read from the executable, `None` if unreadable
+    pub client_build: Option<u32>,
+    /// Build the offset table was made for
+    pub expected_build: u32,
+    pub world: WorldState,
+    /// Visible objects manager, 0 if not in world
+    pub object_manager: u32,
+    /// Objects found walking the manager's list
+    pub objects_walked: usize,
+    pub player_guid: u64,
+    pub player_pointer: Option<u32>,
+    pub last_error: Option<String>,
+    pub config_path: String,
+    pub config_found: bool,
+    /// `None` if the config has not been loaded yet
+    pub config_status: Option<Result<(), String>>,
+}
+
+impl Report {
+    /// Whether the build matches the offsets, treating unknown as a match
+    pub fn build_matches(&self) -> bool {
+        self.client_build
+            .is_none_or(|build| build == self.expected_build)
+    }
+
+    /// Human-readable report, one check per line
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Interact v{} diagnostics", self.version),
+            format!(
+                "Hooks: {} (bootstrap {}, script functions {}, frame tick {}, events {})",
+                if self.hooks_initialized {
+                    "initialized"
+                } else {
+                    "NOT initialized"
+                },
+                enabled(self.bootstrap_hook_enabled),
+                enabled(self.script_hook_enabled),
+                enabled(self.frame_hook_enabled),
+                enabled(self.event_hook_enabled)
+            ),
+            format!(
+                "Scheduled tasks: {}, event handlers: {}",
+                self.scheduled_tasks, self.event_handlers
+            ),
+            format!(
+                "Lua API: {}",
+                if self.lua_initialized {
+                    "initialized"
+                } else {
+                    "NOT initialized"
+                }
+            ),
+            match self.client_build {
+                Some(build) if self.build_matches() => format!("Client build: {build}"),
+                Some(build) => format!(
+                    "Client build: {build} (MISMATCH, offsets are for {})",
+                    self.expected_build
+                ),
+                None => format!(
+                    "Client build: unknown (offsets are for {})",
+                    self.expected_build
+                ),
+            },
+            format!("World: {}", describe_world(self.world)),
+        ];
+
+        if self.world.in_world {
+            lines.push(format!(
+                "Object manager: {:#010x}, {} objects",
+                self.object_manager, self.objects_walked
+            ));
+            lines.push(match self.player_pointer {
+                Some(pointer) => {
+                    format!("Player: GUID {:#018x} at {pointer:#010x}", self.player_guid)
+                }
+                None => format!("Player: GUID {:#018x} not resolved", self.player_guid),
+            });
+        }
+
+        lines.push(format!(
+            "Config: {} ({})",
+            self.config_path,
+            match (&self.config_status, self.config_found) {
+                (None, _) => "not loaded yet".to_owned(),
+                (Some(Ok(())), true) => "loaded".to_owned(),
+                (Some(Ok(())), false) => "not found, using defaults".to_owned(),
+                (Some(Err(e)), _) => format!("error: {e}"),
+            }
+        ));
+        lines.push(format!(
+            "Last error: {}",
+            self.last_error.as_deref().unwrap_or("none")
+        ));
+        lines
+    }
+}
+
+fn enabled(value: bool) -> &'static str {
+    if value {
+        "enabled"
+    } else {
+        "DISABLED"
+    }
+}
+
+/// Summarise the world state, e.g. "in world, dead"
+fn describe_world(world: WorldState) -> String {
+    if !world.in_world {
+        return "not in world".to_owned();
+    }
+    let flags = [
+        (world.loading, "loading"),
+        (world.dead, "dead"),
+        (world.ghost, "ghost"),
+        (world.on_taxi, "on taxi"),
+        (world.in_combat, "in combat"),
+        (world.casting, "casting"),
+    ];
+    let mut parts = vec!["in world"];
+    parts.extend(flags.iter().filter(|(set, _)| *set).map(|(_, name)| *name));
+    parts.join(", ")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy() -> Report {
+        Report {
+            version: "1.2.1",
+            hooks_initialized: true,
+            bootstrap_hook_enabled: true,
+            script_hook_enabled: true,
+            frame_hook_enabled: true,
+            event_hook_enabled: true,
+            scheduled_tasks: 2,
+            event_handlers: 1,
+            lua_initialized: true,
+            client_build: Some(5875),
+            expected_build: 5875,
+            world: WorldState {
+                in_world: true,
+                ..WorldState::default()
+            },
+            object_manager: 0x1234_5678,
+            objects_walked: 42,
+            player_guid: 0x10,
+            player_pointer: Some(0x0800_0000),
+            last_error: None,
+            config_path: "C:\\WoW\\interact.toml".to_owned(),
+            config_found: true,
+            config_status: Some(Ok(())),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Report tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_healthy_report() {
+        let lines = healthy().lines();
+        assert_eq!(
+            lines,
+            [
+                "Interact v1.2.1 diagnostics",
+                "Hooks: initialized (bootstrap enabled, script functions enabled, frame tick enabled, events enabled)",
+                "Scheduled tasks: 2, event handlers: 1",
+                "Lua API: initialized",
+                "Client build: 5875",
+                "World: in world",
+                "Object manager: 0x12345678, 42 objects",
+                "Player: GUID 0x0000000000000010 at 0x08000000",
+                "Config: C:\\WoW\\interact.toml (loaded)",
+                "Last error: none",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_flags_problems() {
+        let report = Report {
+            hooks_initialized: false,
+            script_hook_enabled: false,
+            lua_initialized: false,
+            client_build: Some(6005),
+            player_pointer: None,
+            last_error: Some("Failed to enable hook".to_owned()),
+            config_status: Some(Err("Invalid value for range.distance".to_owned())),
+            ..healthy()
+        };
+        let lines = report.lines();
+        assert!(lines.contains(
+            &"Hooks: NOT initialized (bootstrap enabled, script functions DISABLED, frame tick enabled, events enabled)"
+                .to_owned()
+        ));
+        assert!(lines.contains(&"Lua API: NOT initialized".to_owned()));
+        assert!(lines.contains(&"Client build: 6005 (MISMATCH, offsets are for 5875)".to_owned()));
+        assert!(lines.contains(&"Player: GUID 0x0000000000000010 not resolved".to_owned()));
+        assert!(lines.contains(
+            &"Config: C:\\WoW\\interact.toml (error: Invalid value for range.distance)".to_owned()
+        ));
+        assert!(lines.contains(&"Last error: Failed to enable hook".to_owned()));
+    }
+
+    #[test]
+    fn test_report_out_of_world_skips_object_manager() {
+        let report = Report {
+            world: WorldState::default(),
+            ..healthy()
+        };
+        let lines = report.lines();
+        assert!(lines.contains(&"World: not in world".to_owned()));
+        assert!(!lines.iter().any(|line| line.starts_with("Object manager")));
+        assert!(!lines.iter().any(|line| line.starts_with("Player")));
+    }
+
+    #[test]
+    fn test_report_config_states() {
+        let missing = Report {
+            config_found: false,
+            ..healthy()
+        };
+        assert!(missing
+            .lines()
+            .iter()
+            .any(|line| line.ends_with("(not found, using defaults)")));
+
+        let unloaded = Report {
+            config_status: None,
+            ..healthy()
+        };
+        assert!(unloaded
+            .lines()
+            .iter()
+            .any(|line| line.ends_with("(not loaded yet)")));
+    }
+
+    #[test]
+    fn test_build_matches() {
+        assert!(healthy().build_matches());
+        let unknown = Report {
+            client_build: None,
+            ..healthy()
+        };
+        assert!(unknown.build_matches());
+        let other = Report {
+            client_build: Some(4222),
+            ..healthy()
+        };
+        assert!(!other.build_matches());
+    }
+
+    #[test]
+    fn test_describe_world_lists_flags() {
+        let world = WorldState {
+            in_world: true,
+            ghost: true,
+            in_combat: true,
+            ..WorldState::default()
+        };
+        assert_eq!(describe_world(world), "in world, ghost, in combat");
+    }
+}
@@ -0,0 +1,35 @@
+//! Error types for interact-core
+
+use thiserror::Error;
+
+/// Errors that can occur while loading `interact.toml`
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The file exists but could not be read
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The file is not valid TOML or does not match the expected layout
+    #[error("Failed to parse config file: {0}")]
+    Parse(String),
+
+    /// A value parsed but is out of range
+    #[error("Invalid value for {key}: {message}")]
+    Invalid { key: &'static str, message: String },
+}
+
+/// Errors that can occur while capturing a world snapshot
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// There is no player to capture the world around
+    #[error("Not in world")]
+    NotInWorld,
+
+    /// The snapshot could not be converted to JSON
+    #[error("Failed to serialize snapshot: {0}")]
+    Serialize(String),
+
+    /// The file could not be written
+    #[error("Failed to write snapshot: {0}")]
+    Io(#[from] std::io::Error),
+}
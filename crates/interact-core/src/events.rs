@@ -0,0 +1,516 @@
+//! Client UI events seen by the DLL
+//!
+//! The DLL hooks the client's event signal function, which every UI event
+//! such as `LOOT_OPENED` passes through before the AddOns see it. Events
+//! the DLL cares about are decoded here into `ClientEvent` and handed to the
+//! handlers subscribed to them, so Rust code can tell whether an interaction
+//! opened a loot window or failed with "You are too far away".
+
+use crate::scheduler::panic_message;
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Identifies a subscribed handler, for `Dispatcher::unsubscribe`
+pub type HandlerId = u64;
+
+// =============================================================================
+// Events
+// =============================================================================
+
+/// Client events the DLL subscribes to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    LootOpened,
+    LootClosed,
+    PlayerTargetChanged,
+    UiErrorMessage,
+    PlayerDead,
+    ZoneChangedNewArea,
+}
+
+impl EventKind {
+    pub const ALL: [Self; 6] = [
+        Self::LootOpened,
+        Self::LootClosed,
+        Self::PlayerTargetChanged,
+        Self::UiErrorMessage,
+        Self::PlayerDead,
+        Self::ZoneChangedNewArea,
+    ];
+
+    /// Name the client and AddOns use for the event
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::LootOpened => "LOOT_OPENED",
+            Self::LootClosed => "LOOT_CLOSED",
+            Self::PlayerTargetChanged => "PLAYER_TARGET_CHANGED",
+            Self::UiErrorMessage => "UI_ERROR_MESSAGE",
+            Self::PlayerDead => "PLAYER_DEAD",
+            Self::ZoneChangedNewArea => "ZONE_CHANGED_NEW_AREA",
+        }
+    }
+
+    /// The event with this name, or `None` for events the DLL ignores
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// A decoded client event
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientEvent {
+    LootOpened,
+    LootClosed,
+    PlayerTargetChanged,
+    /// Red error text, e.g. "You are too far away."
+    UiErrorMessage {
+        message: String,
+    },
+    PlayerDead,
+    ZoneChangedNewArea,
+}
+
+impl ClientEvent {
+    /// Build the event from its signalled arguments
+    pub fn new(kind: EventKind, args: &[EventArg]) -> Self {
+        match kind {
+            EventKind::LootOpened => Self::LootOpened,
+            EventKind::LootClosed => Self::LootClosed,
+            EventKind::PlayerTargetChanged => Self::PlayerTargetChanged,
+            EventKind::UiErrorMessage => Self::UiErrorMessage {
+                message: match args.first() {
+                    Some(EventArg::String(message)) => message.clone(),
+                    _ => String::new(),
+                },
+            },
+            EventKind::PlayerDead => Self::PlayerDead,
+            EventKind::ZoneChangedNewArea => Self::ZoneChangedNewArea,
+        }
+    }
+
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::LootOpened => EventKind::LootOpened,
+            Self::LootClosed => EventKind::LootClosed,
+            Self::PlayerTargetChanged => EventKind::PlayerTargetChanged,
+            Self::UiErrorMessage { .. } => EventKind::UiErrorMessage,
+            Self::PlayerDead => EventKind::PlayerDead,
+            Self::ZoneChangedNewArea => EventKind::ZoneChangedNewArea,
+        }
+    }
+}
+
+impl std::fmt::Display for ClientEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UiErrorMessage { message } => write!(f, "{} \"{message}\"", self.kind().name()),
+            _ => f.write_str(self.kind().name()),
+        }
+    }
+}
+
+// =============================================================================
+// Arguments
+// =============================================================================
+
+/// An event argument, as Lua handlers would see it as `arg1`, `arg2`, ...
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventArg {
+    String(String),
+    Number(f64),
+    Nil,
+}
+
+/// Decode the arguments of a signalled event.
+///
+/// The client passes them as C varargs described by a printf-like format
+/// such as `"%s%d"`; `words` are the raw 32-bit stack words after the
+/// format. `%s` is a string pointer read with `read_string`, `%d`, `%i`,
+/// `%u` and `%x` are integers and `%f` a double spanning two words.
+/// Decoding stops at an unknown conversion or when the words run out.
+pub fn parse_args(
+    format: &str,
+    words: &[u32],
+    read_string: impl Fn(u32) -> Option<String>,
+) -> Vec<EventArg> {
+    let mut args = Vec::new();
+    let mut words = words.iter().copied();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        let arg = match chars.next() {
+            Some('s') => words
+                .next()
+                .map(|word| read_string(word).map_or(EventArg::Nil, EventArg::String)),
+            Some('d' | 'i') => words
+                .next()
+                .map(|word| EventArg::Number(f64::from(word.cast_signed()))),
+            Some('u' | 'x') => words.next().map(|word| EventArg::Number(f64::from(word))),
+            Some('f') => match (words.next(), words.next()) {
+                (Some(low), Some(high)) => Some(EventArg::Number(f64::from_bits(
+                    u64::from(high) << 32 | u64::from(low),
+                ))),
+                _ => None,
+            },
+            _ => None,
+        };
+        match arg {
+            Some(arg) => args.push(arg),
+            None => break,
+        }
+    }
+    args
+}
+
+// =============================================================================
+// Dispatcher
+// =============================================================================
+
+/// A subscribed closure
+pub struct Handler {
+    id: HandlerId,
+    kind: EventKind,
+    name: &'static str,
+    run: Box<dyn FnMut(&ClientEvent)>,
+}
+
+impl Handler {
+    pub fn id(&self) -> HandlerId {
+        self.id
+    }
+
+    /// Name used in logs and error reports
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Run the handler, catching a panic and returning its message
+    pub fn call(&mut self, event: &ClientEvent) -> Result<(), String> {
+        panic::catch_unwind(AssertUnwindSafe(|| (self.run)(event))).map_err(|e| panic_message(&*e))
+    }
+}
+
+impl std::fmt::Debug for Handler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handler")
+            .field("id", &self.id)
+            .field("kind", &self.kind)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handlers subscribed to client events.
+///
+/// Like the scheduler, handlers are taken out with `take` and run outside
+/// any borrow of the dispatcher, so they can subscribe and unsubscribe
+/// themselves, then handed back with `restore`. A taken handler does not see
+/// events signalled while it runs. The DLL unsubscribes a handler that
+/// panics, as the scheduler drops a task that panics.
+#[derive(Debug, Default)]
+pub struct Dispatcher {
+    next_id: HandlerId,
+    handlers: Vec<Handler>,
+    /// Handlers handed out by `take` and not yet restored
+    taken: HashSet<HandlerId>,
+    /// Taken handlers unsubscribed while they ran
+    removed: HashSet<HandlerId>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `handler` for every event of `kind`
+    pub fn subscribe(
+        &mut self,
+        kind: EventKind,
+        name: &'static str,
+        handler: impl FnMut(&ClientEvent) + 'static,
+    ) -> HandlerId {
+        self.next_id += 1;
+        self.handlers.push(Handler {
+            id: self.next_id,
+            kind,
+            name,
+            run: Box::new(handler),
+        });
+        self.next_id
+    }
+
+    /// Stop calling a handler. Returns `false` if it is not subscribed.
+    pub fn unsubscribe(&mut self, id: HandlerId) -> bool {
+        if let Some(index) = self.handlers.iter().position(|handler| handler.id == id) {
+            self.handlers.remove(index);
+            true
+        } else if self.taken.contains(&id) {
+            self.removed.insert(id)
+        } else {
+            false
+        }
+    }
+
+    /// Whether any handler is subscribed to `kind`, so events nobody listens
+    /// to are not decoded
+    pub fn wants(&self, kind: EventKind) -> bool {
+        self.handlers.iter().any(|handler| handler.kind == kind)
+    }
+
+    /// Number of subscribed handlers, running ones included
+    pub fn len(&self) -> usize {
+        self.handlers.len() + self.taken.len() - self.removed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove and return the handlers of `kind`, in subscription order
+    pub fn take(&mut self, kind: EventKind) -> Vec<Handler> {
+        let (taken, rest): (Vec<Handler>, Vec<Handler>) = std::mem::take(&mut self.handlers)
+            .into_iter()
+            .partition(|handler| handler.kind == kind);
+        self.handlers = rest;
+        self.taken.extend(taken.iter().map(|handler| handler.id));
+        taken
+    }
+
+    /// Hand back handlers returned by `take`, dropping any unsubscribed while
+    /// they ran. They keep their place ahead of handlers subscribed since.
+    pub fn restore(&mut self, handlers: Vec<Handler>) {
+        let kept: Vec<Handler> = handlers
+            .into_iter()
+            .filter(|handler| {
+                self.taken.remove(&handler.id);
+                !self.removed.remove(&handler.id)
+            })
+            .collect();
+        self.handlers.splice(0..0, kept);
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Events each named handler saw, in order
+    type Log = Rc<RefCell<Vec<(&'static str, ClientEvent)>>>;
+
+    fn logger(log: &Log, name: &'static str) -> impl FnMut(&ClientEvent) + 'static {
+        let log = Rc::clone(log);
+        move |event| log.borrow_mut().push((name, event.clone()))
+    }
+
+    /// Dispatch one event, as the DLL does. Returns the names of handlers
+    /// that panicked.
+    fn signal(dispatcher: &Rc<RefCell<Dispatcher>>, event: &ClientEvent) -> Vec<&'static str> {
+        let mut handlers = dispatcher.borrow_mut().take(event.kind());
+        let mut panicked = Vec::new();
+        for handler in &mut handlers {
+            if handler.call(event).is_err() {
+                panicked.push(handler.name());
+                dispatcher.borrow_mut().unsubscribe(handler.id());
+            }
+        }
+        dispatcher.borrow_mut().restore(handlers);
+        panicked
+    }
+
+    fn setup() -> (Rc<RefCell<Dispatcher>>, Log) {
+        (Rc::default(), Log::default())
+    }
+
+    /// Strings stored at fake addresses 1 and 2
+    fn read_string(addr: u32) -> Option<String> {
+        match addr {
+            1 => Some("You are too far away.".to_owned()),
+            2 => Some("player".to_owned()),
+            _ => None,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Event tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_event_names() {
+        for kind in EventKind::ALL {
+            assert_eq!(EventKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(
+            EventKind::from_name("UI_ERROR_MESSAGE"),
+            Some(EventKind::UiErrorMessage)
+        );
+        assert_eq!(EventKind::from_name("CHAT_MSG_SAY"), None);
+        assert_eq!(EventKind::from_name("loot_opened"), None);
+    }
+
+    #[test]
+    fn test_client_event_from_args() {
+        let error = ClientEvent::new(
+            EventKind::UiErrorMessage,
+            &[EventArg::String("You are too far away.".to_owned())],
+        );
+        assert_eq!(
+            error,
+            ClientEvent::UiErrorMessage {
+                message: "You are too far away.".to_owned()
+            }
+        );
+        assert_eq!(error.kind(), EventKind::UiErrorMessage);
+        assert_eq!(
+            error.to_string(),
+            "UI_ERROR_MESSAGE \"You are too far away.\""
+        );
+        assert_eq!(
+            ClientEvent::new(EventKind::UiErrorMessage, &[]),
+            ClientEvent::UiErrorMessage {
+                message: String::new()
+            }
+        );
+        for kind in EventKind::ALL {
+            assert_eq!(ClientEvent::new(kind, &[]).kind(), kind);
+        }
+        assert_eq!(ClientEvent::LootOpened.to_string(), "LOOT_OPENED");
+    }
+
+    // -------------------------------------------------------------------------
+    // Argument tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args("", &[1, 2], read_string), []);
+        assert_eq!(
+            parse_args("%s", &[1], read_string),
+            [EventArg::String("You are too far away.".to_owned())]
+        );
+        assert_eq!(
+            parse_args("%s%d%u%s", &[2, (-5i32) as u32, 7, 0], read_string),
+            [
+                EventArg::String("player".to_owned()),
+                EventArg::Number(-5.0),
+                EventArg::Number(7.0),
+                EventArg::Nil,
+            ]
+        );
+        let bits = 2.5f64.to_bits();
+        assert_eq!(
+            parse_args("%f%d", &[bits as u32, (bits >> 32) as u32, 3], read_string),
+            [EventArg::Number(2.5), EventArg::Number(3.0)]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_stops_early() {
+        // Unknown conversion
+        assert_eq!(
+            parse_args("%d%q%d", &[1, 2, 3], read_string),
+            [EventArg::Number(1.0)]
+        );
+        // Not enough words, including half a double
+        assert_eq!(
+            parse_args("%d%d", &[4], read_string),
+            [EventArg::Number(4.0)]
+        );
+        assert_eq!(parse_args("%f", &[0], read_string), []);
+    }
+
+    // -------------------------------------------------------------------------
+    // Dispatcher tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_dispatch_by_kind_in_order() {
+        let (dispatcher, log) = setup();
+        dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::LootOpened, "a", logger(&log, "a"));
+        dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::LootClosed, "closed", logger(&log, "closed"));
+        dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::LootOpened, "b", logger(&log, "b"));
+        assert!(dispatcher.borrow().wants(EventKind::LootOpened));
+        assert!(!dispatcher.borrow().wants(EventKind::PlayerDead));
+
+        signal(&dispatcher, &ClientEvent::LootOpened);
+        signal(&dispatcher, &ClientEvent::PlayerDead);
+        signal(&dispatcher, &ClientEvent::LootOpened);
+        let names: Vec<_> = log.borrow().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["a", "b", "a", "b"]);
+        assert_eq!(dispatcher.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let (dispatcher, log) = setup();
+        let id =
+            dispatcher
+                .borrow_mut()
+                .subscribe(EventKind::PlayerDead, "dead", logger(&log, "dead"));
+        assert!(dispatcher.borrow_mut().unsubscribe(id));
+        assert!(!dispatcher.borrow_mut().unsubscribe(id));
+        signal(&dispatcher, &ClientEvent::PlayerDead);
+        assert!(log.borrow().is_empty());
+        assert!(dispatcher.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_handler_can_unsubscribe_and_subscribe_while_running() {
+        let (dispatcher, log) = setup();
+        let id = Rc::new(RefCell::new(0));
+        let once = {
+            let dispatcher = Rc::clone(&dispatcher);
+            let log = Rc::clone(&log);
+            let id = Rc::clone(&id);
+            move |event: &ClientEvent| {
+                log.borrow_mut().push(("once", event.clone()));
+                let mut dispatcher = dispatcher.borrow_mut();
+                assert!(dispatcher.unsubscribe(*id.borrow()));
+                dispatcher.subscribe(EventKind::LootClosed, "next", logger(&log, "next"));
+            }
+        };
+        *id.borrow_mut() = dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::LootClosed, "once", once);
+        dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::LootClosed, "always", logger(&log, "always"));
+
+        signal(&dispatcher, &ClientEvent::LootClosed);
+        signal(&dispatcher, &ClientEvent::LootClosed);
+        let names: Vec<_> = log.borrow().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["once", "always", "always", "next"]);
+        assert_eq!(dispatcher.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_panicking_handler_is_isolated() {
+        let (dispatcher, log) = setup();
+        dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::UiErrorMessage, "broken", |_| panic!("boom"));
+        dispatcher
+            .borrow_mut()
+            .subscribe(EventKind::UiErrorMessage, "fine", logger(&log, "fine"));
+        let event = ClientEvent::UiErrorMessage {
+            message: "Inventory is full.".to_owned(),
+        };
+        assert_eq!(signal(&dispatcher, &event), ["broken"]);
+        assert_eq!(signal(&dispatcher, &event), Vec::<&str>::new());
+        assert_eq!(*log.borrow(), [("fine", event.clone()), ("fine", event)]);
+        assert_eq!(dispatcher.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,129 @@
+//! Per-frame state
+//!
+//! The DLL counts the client's frames from its frame tick hook. Values that
+//! are expensive to build and only valid for one frame, such as the walked
+//! list of visible objects, are cached against that frame number so every
+//! script function called in the same frame shares one copy.
+
+// =============================================================================
+// Frame Cache
+// =============================================================================
+
+/// A value built at most once per frame
+#[derive(Debug)]
+pub struct FrameCache<T> {
+    /// Frame the value was built in, and the value
+    built: Option<(u32, T)>,
+}
+
+impl<T> Default for FrameCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FrameCache<T> {
+    pub const fn new() -> Self {
+        Self { built: None }
+    }
+
+    /// Frame the cached value was built in, if any
+    pub fn frame(&self) -> Option<u32> {
+        self.built.as_ref().map(|(frame, _)| *frame)
+    }
+
+    /// Drop the cached value
+    pub fn clear(&mut self) {
+        self.built = None;
+    }
+}
+
+impl<T: Clone> FrameCache<T> {
+    /// The value for `frame`, building it if the cached one is from another
+    /// frame.
+    ///
+    /// Without a frame number (the tick hook is not running) there is no
+    /// way to tell when the value goes stale, so it is built every time and
+    /// not kept.
+    pub fn get_or_build(&mut self, frame: Option<u32>, build: impl FnOnce() -> T) -> T {
+        let Some(frame) = frame else {
+            self.built = None;
+            return build();
+        };
+        match &self.built {
+            Some((built_in, value)) if *built_in == frame => value.clone(),
+            _ => {
+                let value = build();
+                self.built = Some((frame, value.clone()));
+                value
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// An empty cache and a counter of how often it was built
+    fn counting() -> (FrameCache<u32>, Cell<u32>) {
+        (FrameCache::new(), Cell::new(0))
+    }
+
+    fn get(cache: &mut FrameCache<u32>, builds: &Cell<u32>, frame: Option<u32>) -> u32 {
+        cache.get_or_build(frame, || {
+            builds.set(builds.get() + 1);
+            builds.get()
+        })
+    }
+
+    // -------------------------------------------------------------------------
+    // Frame cache tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_built_once_per_frame() {
+        let (mut cache, builds) = counting();
+        assert_eq!(get(&mut cache, &builds, Some(7)), 1);
+        assert_eq!(get(&mut cache, &builds, Some(7)), 1);
+        assert_eq!(get(&mut cache, &builds, Some(7)), 1);
+        assert_eq!(builds.get(), 1);
+        assert_eq!(cache.frame(), Some(7));
+    }
+
+    #[test]
+    fn test_rebuilt_on_a_new_frame() {
+        let (mut cache, builds) = counting();
+        assert_eq!(get(&mut cache, &builds, Some(7)), 1);
+        assert_eq!(get(&mut cache, &builds, Some(8)), 2);
+        assert_eq!(get(&mut cache, &builds, Some(8)), 2);
+        // Any other frame number is stale, including a wrapped counter
+        assert_eq!(get(&mut cache, &builds, Some(0)), 3);
+        assert_eq!(cache.frame(), Some(0));
+    }
+
+    #[test]
+    fn test_without_frame_number_builds_every_time() {
+        let (mut cache, builds) = counting();
+        assert_eq!(get(&mut cache, &builds, Some(7)), 1);
+        assert_eq!(get(&mut cache, &builds, None), 2);
+        assert_eq!(get(&mut cache, &builds, None), 3);
+        assert_eq!(cache.frame(), None);
+        // The value built without a frame is not reused once frames resume
+        assert_eq!(get(&mut cache, &builds, Some(7)), 4);
+    }
+
+    #[test]
+    fn test_clear() {
+        let (mut cache, builds) = counting();
+        get(&mut cache, &builds, Some(7));
+        cache.clear();
+        assert_eq!(cache.frame(), None);
+        assert_eq!(get(&mut cache, &builds, Some(7)), 2);
+    }
+}
@@ -0,0 +1,907 @@
+//! Game types for WoW 1.12.1
+//!
+//! Object types, positions, the local player's world state, the blacklist
+//! and name filters. Nothing here reads game memory; the DLL's `game` module
+//! re-exports these types next to the functions that fill them in.
+
+use crate::config::DEFAULT_BLACKLIST;
+use crate::descriptors::UnitFlags;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::{PoisonError, RwLock};
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Object types in WoW
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObjectType {
+    #[default]
+    None = 0,
+    Item = 1,
+    Container = 2,
+    Unit = 3,
+    Player = 4,
+    GameObject = 5,
+    DynamicObject = 6,
+    Corpse = 7,
+}
+
+impl From<u32> for ObjectType {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => Self::Item,
+            2 => Self::Container,
+            3 => Self::Unit,
+            4 => Self::Player,
+            5 => Self::GameObject,
+            6 => Self::DynamicObject,
+            7 => Self::Corpse,
+            _ => Self::None,
+        }
+    }
+}
+
+/// 3D Vector for positions
+/// Note: In WoW's coordinate system, Y comes before X in memory layout
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct C3Vector {
+    pub y: f32,
+    pub x: f32,
+    pub z: f32,
+}
+
+impl C3Vector {
+    /// Calculate 3D Euclidean distance to another point
+    #[inline]
+    pub fn distance(&self, other: &C3Vector) -> f32 {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+        let dz = other.z - self.z;
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// Calculate horizontal (XY plane) distance to another point
+    #[inline]
+    pub fn distance_2d(&self, other: &C3Vector) -> f32 {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Rotate around the Z axis by `angle` radians (counter-clockwise)
+    #[inline]
+    #[must_use]
+    pub fn rotate_z(&self, angle: f32) -> C3Vector {
+        let (sin, cos) = angle.sin_cos();
+        C3Vector {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+            z: self.z,
+        }
+    }
+
+    /// Angle of the direction from `self` to `other` in the XY plane,
+    /// in radians in `[0, 2π)` (WoW facing convention)
+    #[inline]
+    pub fn angle_to(&self, other: &C3Vector) -> f32 {
+        normalize_angle((other.y - self.y).atan2(other.x - self.x))
+    }
+
+    /// Absolute angle in radians (`[0, π]`) between a facing and the
+    /// direction from `self` to `target`; 0 means straight ahead
+    #[inline]
+    pub fn facing_offset(&self, facing: f32, target: &C3Vector) -> f32 {
+        angle_difference(facing, self.angle_to(target))
+    }
+
+    /// Convert transport-local coordinates to world coordinates.
+    ///
+    /// `origin` and `facing` are the transport's world position and
+    /// orientation; `self` is the offset relative to the transport.
+    #[inline]
+    #[must_use]
+    pub fn local_to_world(&self, origin: &C3Vector, facing: f32) -> C3Vector {
+        let rotated = self.rotate_z(facing);
+        C3Vector {
+            x: origin.x + rotated.x,
+            y: origin.y + rotated.y,
+            z: origin.z + rotated.z,
+        }
+    }
+}
+
+/// Normalize an angle in radians to `[0, 2π)`
+#[inline]
+pub fn normalize_angle(angle: f32) -> f32 {
+    let normalized = angle.rem_euclid(std::f32::consts::TAU);
+    // rem_euclid can round up to exactly TAU for tiny negative inputs
+    if normalized >= std::f32::consts::TAU {
+        0.0
+    } else {
+        normalized
+    }
+}
+
+/// Smallest absolute difference between two angles, in `[0, π]`
+#[inline]
+pub fn angle_difference(a: f32, b: f32) -> f32 {
+    let diff = normalize_angle(a - b);
+    if diff > std::f32::consts::PI {
+        std::f32::consts::TAU - diff
+    } else {
+        diff
+    }
+}
+
+// =============================================================================
+// Blacklisted Object IDs
+// =============================================================================
+
+/// Game object IDs that should not be auto-interacted with. Starts as
+/// `config::DEFAULT_BLACKLIST` and is replaced when the config is loaded.
+static BLACKLIST: Lazy<RwLock<HashSet<u32>>> =
+    Lazy::new(|| RwLock::new(DEFAULT_BLACKLIST.iter().copied().collect()));
+
+/// Check if a game object ID is blacklisted
+#[inline]
+pub fn is_blacklisted(id: u32) -> bool {
+    BLACKLIST
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .contains(&id)
+}
+
+/// Replace the blacklist
+pub fn set_blacklist(ids: &[u32]) {
+    *BLACKLIST.write().unwrap_or_else(PoisonError::into_inner) = ids.iter().copied().collect();
+}
+
+/// Blacklisted IDs in ascending order
+pub fn blacklist() -> Vec<u32> {
+    let mut ids: Vec<u32> = BLACKLIST
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .copied()
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Get a copy of the blacklist set (for testing)
+#[cfg(test)]
+pub fn get_blacklist() -> HashSet<u32> {
+    BLACKLIST
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+// =============================================================================
+// Name Filters
+// =============================================================================
+
+/// Include/exclude patterns matched against object names.
+///
+/// Patterns are case-insensitive and support `*` (any run of characters)
+/// and `?` (any single character). An empty include list allows every name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl NameFilter {
+    /// Build a filter from comma-separated include and exclude pattern lists
+    pub fn parse(include: &str, exclude: &str) -> Self {
+        Self {
+            include: split_patterns(include),
+            exclude: split_patterns(exclude),
+        }
+    }
+
+    /// Include patterns, lowercased
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    /// Exclude patterns, lowercased
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    /// Whether the filter has no patterns (names never need to be read)
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Check if an object with the given name may be selected.
+    ///
+    /// Unnamed objects never match an include pattern and never match an
+    /// exclude pattern.
+    pub fn allows(&self, name: Option<&str>) -> bool {
+        let Some(name) = name else {
+            return self.include.is_empty();
+        };
+        let name = name.to_lowercase();
+
+        if self.exclude.iter().any(|p| glob_match(p, &name)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|p| glob_match(p, &name))
+    }
+}
+
+/// Split a comma-separated pattern list, trimming and lowercasing entries
+fn split_patterns(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Match `text` against a glob `pattern` supporting `*` and `?`
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern and the text index it matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// =============================================================================
+// World State
+// =============================================================================
+
+/// Player flag: player has released their spirit (`PLAYER_FLAGS_GHOST`)
+const PLAYER_FLAG_GHOST: u32 = 0x0000_0010;
+
+/// Snapshot of the local player's state, read once per interaction.
+///
+/// `IsIngame` alone is not enough: during zoning and teleports the flag can
+/// be set while the object manager or the player object is not yet valid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Independent client flags, not a state machine
+pub struct WorldState {
+    /// `IsIngame` flag is set
+    pub in_world: bool,
+    /// In world, but the object manager or player object is not resolvable yet
+    pub loading: bool,
+    /// Player is dead and has not released their spirit
+    pub dead: bool,
+    /// Player is a ghost (released spirit)
+    pub ghost: bool,
+    /// Player is on a flight path
+    pub on_taxi: bool,
+    /// Player is in combat
+    pub in_combat: bool,
+    /// Player is casting or channeling a spell
+    pub casting: bool,
+}
+
+impl WorldState {
+    /// State used while the client reports `IsIngame` but the player object
+    /// cannot be resolved yet.
+    pub const LOADING: Self = Self {
+        in_world: true,
+        loading: true,
+        dead: false,
+        ghost: false,
+        on_taxi: false,
+        in_combat: false,
+        casting: false,
+    };
+
+    /// Build the state from the player's raw fields.
+    pub fn from_player_fields(
+        health: i32,
+        unit_flags: UnitFlags,
+        player_flags: u32,
+        casting_spell: u32,
+        channel_spell: u32,
+    ) -> Self {
+        let ghost = (player_flags & PLAYER_FLAG_GHOST) != 0;
+        Self {
+            in_world: true,
+            loading: false,
+            dead: health <= 0 && !ghost,
+            ghost,
+            on_taxi: unit_flags.contains(UnitFlags::TAXI_FLIGHT),
+            in_combat: unit_flags.contains(UnitFlags::IN_COMBAT),
+            casting: casting_spell != 0 || channel_spell != 0,
+        }
+    }
+
+    /// Whether any interaction should be attempted at all.
+    ///
+    /// Interacting while dead, on a flight path or mid-cast either fails
+    /// client-side or interrupts the player, so the selector bails out.
+    pub fn can_interact(&self) -> bool {
+        self.in_world && !self.loading && !self.dead && !self.on_taxi && !self.casting
+    }
+
+    /// Whether corpses and game objects may be interacted with.
+    ///
+    /// Ghosts can only talk to living NPCs (e.g. the spirit healer).
+    pub fn can_loot(&self) -> bool {
+        !self.ghost
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)] // Exact float comparisons are intentional in these tests
+
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // C3Vector tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_c3vector_distance_same_point() {
+        let a = C3Vector {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        let b = C3Vector {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        assert!((a.distance(&b) - 0.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_c3vector_distance_unit_x() {
+        let a = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert!((a.distance(&b) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_c3vector_distance_unit_y() {
+        let a = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        assert!((a.distance(&b) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_c3vector_distance_unit_z() {
+        let a = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+        assert!((a.distance(&b) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_c3vector_distance_3d() {
+        // 3-4-5 triangle in 3D: sqrt(3^2 + 4^2 + 0^2) = 5
+        let a = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 3.0,
+            y: 4.0,
+            z: 0.0,
+        };
+        assert!((a.distance(&b) - 5.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_c3vector_distance_3d_diagonal() {
+        // sqrt(1^2 + 1^2 + 1^2) = sqrt(3) ≈ 1.732
+        let a = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+        };
+        let expected = 3.0_f32.sqrt();
+        assert!((a.distance(&b) - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_c3vector_distance_symmetric() {
+        let a = C3Vector {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        let b = C3Vector {
+            x: 4.0,
+            y: 6.0,
+            z: 8.0,
+        };
+        assert!((a.distance(&b) - b.distance(&a)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_c3vector_distance_negative_coords() {
+        let a = C3Vector {
+            x: -1.0,
+            y: -1.0,
+            z: -1.0,
+        };
+        let b = C3Vector {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+        };
+        // Distance should be sqrt(4 + 4 + 4) = sqrt(12) ≈ 3.464
+        let expected = 12.0_f32.sqrt();
+        assert!((a.distance(&b) - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_c3vector_distance_within_5_yards() {
+        let player = C3Vector {
+            x: 100.0,
+            y: 200.0,
+            z: 50.0,
+        };
+        let nearby = C3Vector {
+            x: 103.0,
+            y: 204.0,
+            z: 50.0,
+        };
+        // Distance = sqrt(9 + 16 + 0) = 5.0 exactly
+        assert!(player.distance(&nearby) <= 5.0);
+    }
+
+    #[test]
+    fn test_c3vector_distance_beyond_5_yards() {
+        let player = C3Vector {
+            x: 100.0,
+            y: 200.0,
+            z: 50.0,
+        };
+        let far = C3Vector {
+            x: 106.0,
+            y: 200.0,
+            z: 50.0,
+        };
+        // Distance = 6.0
+        assert!(player.distance(&far) > 5.0);
+    }
+
+    #[test]
+    fn test_c3vector_distance_2d_ignores_z() {
+        let a = C3Vector {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 3.0,
+            y: 4.0,
+            z: 100.0,
+        };
+        assert!((a.distance_2d(&b) - 5.0).abs() < f32::EPSILON);
+        assert!(a.distance_2d(&b) < a.distance(&b));
+    }
+
+    #[test]
+    fn test_c3vector_rotate_z_zero_is_identity() {
+        let v = C3Vector {
+            x: 3.0,
+            y: -2.0,
+            z: 7.0,
+        };
+        let r = v.rotate_z(0.0);
+        assert_eq!((r.x, r.y, r.z), (3.0, -2.0, 7.0));
+    }
+
+    #[test]
+    fn test_c3vector_rotate_z_quarter_turn() {
+        // +X rotated 90 degrees counter-clockwise becomes +Y
+        let v = C3Vector {
+            x: 1.0,
+            y: 0.0,
+            z: 5.0,
+        };
+        let r = v.rotate_z(std::f32::consts::FRAC_PI_2);
+        assert!(r.x.abs() < 0.0001);
+        assert!((r.y - 1.0).abs() < 0.0001);
+        assert_eq!(r.z, 5.0);
+    }
+
+    #[test]
+    fn test_c3vector_rotate_z_preserves_length() {
+        let origin = C3Vector::default();
+        let v = C3Vector {
+            x: 3.0,
+            y: 4.0,
+            z: 0.0,
+        };
+        let r = v.rotate_z(1.234);
+        assert!((origin.distance(&r) - 5.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_c3vector_local_to_world_no_rotation() {
+        let transport = C3Vector {
+            x: 100.0,
+            y: 200.0,
+            z: 10.0,
+        };
+        let local = C3Vector {
+            x: 2.0,
+            y: -1.0,
+            z: 3.0,
+        };
+        let world = local.local_to_world(&transport, 0.0);
+        assert_eq!((world.x, world.y, world.z), (102.0, 199.0, 13.0));
+    }
+
+    #[test]
+    fn test_c3vector_local_to_world_half_turn() {
+        // A boat facing the opposite way mirrors the local X/Y offsets
+        let transport = C3Vector {
+            x: 100.0,
+            y: 200.0,
+            z: 0.0,
+        };
+        let local = C3Vector {
+            x: 5.0,
+            y: 2.0,
+            z: 1.0,
+        };
+        let world = local.local_to_world(&transport, std::f32::consts::PI);
+        assert!((world.x - 95.0).abs() < 0.0001);
+        assert!((world.y - 198.0).abs() < 0.0001);
+        assert_eq!(world.z, 1.0);
+    }
+
+    #[test]
+    fn test_c3vector_local_to_world_same_transport_distance() {
+        // Two units on the same transport keep their local distance in world space
+        let transport = C3Vector {
+            x: -1200.0,
+            y: 300.0,
+            z: 5.0,
+        };
+        let facing = 2.5;
+        let a = C3Vector {
+            x: 1.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        let b = C3Vector {
+            x: 4.0,
+            y: 5.0,
+            z: 0.0,
+        };
+        let world_a = a.local_to_world(&transport, facing);
+        let world_b = b.local_to_world(&transport, facing);
+        assert!((world_a.distance(&world_b) - a.distance(&b)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normalize_angle() {
+        use std::f32::consts::{PI, TAU};
+        assert_eq!(normalize_angle(0.0), 0.0);
+        assert!((normalize_angle(-PI / 2.0) - 3.0 * PI / 2.0).abs() < 0.0001);
+        assert!((normalize_angle(TAU + 1.0) - 1.0).abs() < 0.0001);
+        assert!(normalize_angle(-1e-9) < TAU);
+    }
+
+    #[test]
+    fn test_angle_difference_wraps() {
+        use std::f32::consts::{PI, TAU};
+        assert!((angle_difference(0.1, TAU - 0.1) - 0.2).abs() < 0.0001);
+        assert!((angle_difference(0.0, PI) - PI).abs() < 0.0001);
+        assert!((angle_difference(PI / 2.0, 0.0) - PI / 2.0).abs() < 0.0001);
+        assert_eq!(angle_difference(1.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_c3vector_angle_to_axes() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        let origin = C3Vector::default();
+        let east = C3Vector {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let north = C3Vector {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        let west = C3Vector {
+            x: -1.0,
+            y: 0.0,
+            z: 9.0,
+        };
+        assert!(origin.angle_to(&east).abs() < 0.0001);
+        assert!((origin.angle_to(&north) - FRAC_PI_2).abs() < 0.0001);
+        assert!((origin.angle_to(&west) - PI).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_c3vector_facing_offset_front_and_behind() {
+        use std::f32::consts::PI;
+        let player = C3Vector {
+            x: 10.0,
+            y: 10.0,
+            z: 0.0,
+        };
+        let ahead = C3Vector {
+            x: 13.0,
+            y: 10.0,
+            z: 0.0,
+        };
+        let behind = C3Vector {
+            x: 7.0,
+            y: 10.0,
+            z: 0.0,
+        };
+        assert!(player.facing_offset(0.0, &ahead) < 0.0001);
+        assert!((player.facing_offset(0.0, &behind) - PI).abs() < 0.0001);
+        // Facing west flips the result
+        assert!((player.facing_offset(PI, &ahead) - PI).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_c3vector_default() {
+        let v = C3Vector::default();
+        assert_eq!(v.x, 0.0);
+        assert_eq!(v.y, 0.0);
+        assert_eq!(v.z, 0.0);
+    }
+
+    // -------------------------------------------------------------------------
+    // ObjectType tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_object_type_from_valid_values() {
+        assert_eq!(ObjectType::from(0), ObjectType::None);
+        assert_eq!(ObjectType::from(1), ObjectType::Item);
+        assert_eq!(ObjectType::from(2), ObjectType::Container);
+        assert_eq!(ObjectType::from(3), ObjectType::Unit);
+        assert_eq!(ObjectType::from(4), ObjectType::Player);
+        assert_eq!(ObjectType::from(5), ObjectType::GameObject);
+        assert_eq!(ObjectType::from(6), ObjectType::DynamicObject);
+        assert_eq!(ObjectType::from(7), ObjectType::Corpse);
+    }
+
+    #[test]
+    fn test_object_type_from_invalid_values() {
+        assert_eq!(ObjectType::from(8), ObjectType::None);
+        assert_eq!(ObjectType::from(100), ObjectType::None);
+        assert_eq!(ObjectType::from(u32::MAX), ObjectType::None);
+    }
+
+    #[test]
+    fn test_object_type_default() {
+        assert_eq!(ObjectType::default(), ObjectType::None);
+    }
+
+    #[test]
+    fn test_object_type_equality() {
+        assert_eq!(ObjectType::Unit, ObjectType::Unit);
+        assert_ne!(ObjectType::Unit, ObjectType::Player);
+        assert_ne!(ObjectType::GameObject, ObjectType::None);
+    }
+
+    // -------------------------------------------------------------------------
+    // WorldState tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_world_state_default_cannot_interact() {
+        let state = WorldState::default();
+        assert!(!state.in_world);
+        assert!(!state.can_interact());
+    }
+
+    #[test]
+    fn test_world_state_loading_cannot_interact() {
+        let state = WorldState::LOADING;
+        assert!(state.in_world);
+        assert!(!state.can_interact());
+    }
+
+    #[test]
+    fn test_world_state_alive_idle_can_interact() {
+        let state = WorldState::from_player_fields(100, UnitFlags::default(), 0, 0, 0);
+        assert!(state.can_interact());
+        assert!(state.can_loot());
+        assert!(!state.dead && !state.ghost && !state.on_taxi);
+    }
+
+    #[test]
+    fn test_world_state_in_combat_can_interact() {
+        let state = WorldState::from_player_fields(100, UnitFlags::IN_COMBAT, 0, 0, 0);
+        assert!(state.in_combat);
+        assert!(state.can_interact());
+    }
+
+    #[test]
+    fn test_world_state_dead_cannot_interact() {
+        let state = WorldState::from_player_fields(0, UnitFlags::default(), 0, 0, 0);
+        assert!(state.dead);
+        assert!(!state.can_interact());
+    }
+
+    #[test]
+    fn test_world_state_ghost_cannot_loot() {
+        let state =
+            WorldState::from_player_fields(1, UnitFlags::default(), PLAYER_FLAG_GHOST, 0, 0);
+        assert!(state.ghost);
+        assert!(!state.dead);
+        assert!(state.can_interact());
+        assert!(!state.can_loot());
+    }
+
+    #[test]
+    fn test_world_state_taxi_cannot_interact() {
+        let state = WorldState::from_player_fields(100, UnitFlags::TAXI_FLIGHT, 0, 0, 0);
+        assert!(state.on_taxi);
+        assert!(!state.can_interact());
+    }
+
+    #[test]
+    fn test_world_state_casting_or_channeling_cannot_interact() {
+        assert!(
+            !WorldState::from_player_fields(100, UnitFlags::default(), 0, 133, 0).can_interact()
+        );
+        assert!(
+            !WorldState::from_player_fields(100, UnitFlags::default(), 0, 0, 746).can_interact()
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // NameFilter tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_glob_match_exact() {
+        assert!(glob_match("mailbox", "mailbox"));
+        assert!(!glob_match("mailbox", "mailboxes"));
+        assert!(!glob_match("mailbox", "mail"));
+    }
+
+    #[test]
+    fn test_glob_match_wildcards() {
+        assert!(glob_match("*chest", "battered chest"));
+        assert!(glob_match("copper*", "copper vein"));
+        assert!(glob_match("*vein*", "tin vein"));
+        assert!(glob_match("t?n vein", "tin vein"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+        assert!(!glob_match("?", ""));
+    }
+
+    #[test]
+    fn test_name_filter_empty_allows_all() {
+        let filter = NameFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.allows(Some("Anything")));
+        assert!(filter.allows(None));
+    }
+
+    #[test]
+    fn test_name_filter_parse_trims_and_skips_empty() {
+        let filter = NameFilter::parse(" Peacebloom , ,*Vein ", "");
+        assert!(!filter.is_empty());
+        assert!(filter.allows(Some("peacebloom")));
+        assert!(filter.allows(Some("Copper Vein")));
+        assert!(!filter.allows(Some("Silverleaf")));
+    }
+
+    #[test]
+    fn test_name_filter_include_rejects_unnamed() {
+        let filter = NameFilter::parse("*Chest", "");
+        assert!(filter.allows(Some("Battered Chest")));
+        assert!(!filter.allows(None));
+    }
+
+    #[test]
+    fn test_name_filter_exclude_case_insensitive() {
+        let filter = NameFilter::parse("", "dusty*");
+        assert!(!filter.allows(Some("Dusty Crate")));
+        assert!(filter.allows(Some("Battered Chest")));
+        assert!(filter.allows(None));
+    }
+
+    #[test]
+    fn test_name_filter_exclude_wins_over_include() {
+        let filter = NameFilter::parse("*Chest", "Solid Chest");
+        assert!(filter.allows(Some("Battered Chest")));
+        assert!(!filter.allows(Some("Solid Chest")));
+    }
+
+    // -------------------------------------------------------------------------
+    // Blacklist tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_blacklist_contains_expected_ids() {
+        assert!(is_blacklisted(179830));
+        assert!(is_blacklisted(179831));
+        assert!(is_blacklisted(179785));
+        assert!(is_blacklisted(179786));
+    }
+
+    #[test]
+    fn test_blacklist_size() {
+        let blacklist = get_blacklist();
+        assert_eq!(blacklist.len(), 4);
+    }
+
+    #[test]
+    fn test_blacklist_sorted() {
+        assert_eq!(blacklist(), [179785, 179786, 179830, 179831]);
+    }
+
+    #[test]
+    fn test_blacklist_does_not_contain_other_ids() {
+        assert!(!is_blacklisted(0));
+        assert!(!is_blacklisted(1));
+        assert!(!is_blacklisted(179829));
+        assert!(!is_blacklisted(179832));
+        assert!(!is_blacklisted(u32::MAX));
+    }
+}
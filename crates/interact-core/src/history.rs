@@ -0,0 +1,329 @@
+//! Recent interactions and what came of them
+//!
+//! Every interaction `InteractNearest` performs is recorded with an outcome
+//! that starts out pending. Client events seen within `OUTCOME_WINDOW_MS`
+//! settle it: a loot window opening, or the red error text the client shows
+//! for "You are too far away." and the like. If neither arrives in time the
+//! interaction had no visible result. `InteractGetHistory(n)` returns the
+//! most recent entries.
+
+use crate::config::Tier;
+use crate::events::ClientEvent;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Interactions kept; older ones are dropped
+pub const HISTORY_CAPACITY: usize = 50;
+
+/// How long after an interaction an event still counts as its outcome
+pub const OUTCOME_WINDOW_MS: u64 = 1000;
+
+// =============================================================================
+// Interactions
+// =============================================================================
+
+/// What came of an interaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Still within the outcome window
+    Pending,
+    LootOpened,
+    /// The client showed this error message
+    Error(String),
+    /// The window passed without a loot window or error
+    Nothing,
+}
+
+impl Outcome {
+    /// Name returned to Lua as `outcome`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::LootOpened => "loot",
+            Self::Error(_) => "error",
+            Self::Nothing => "none",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pending => f.write_str("pending"),
+            Self::LootOpened => f.write_str("loot opened"),
+            Self::Error(message) => write!(f, "error \"{message}\""),
+            Self::Nothing => f.write_str("nothing"),
+        }
+    }
+}
+
+/// One interaction performed by `InteractNearest`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interaction {
+    /// Milliseconds since the system started; Lua gets seconds, as from
+    /// `GetTime()`
+    pub time_ms: u64,
+    pub guid: u64,
+    pub entry: u32,
+    /// Tier the object was selected from
+    pub category: Option<Tier>,
+    pub distance: f32,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Interaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "guid {:#018x}, entry {}, {}, {:.2} yd: {}",
+            self.guid,
+            self.entry,
+            self.category.map_or("unknown", Tier::name),
+            self.distance,
+            self.outcome
+        )
+    }
+}
+
+// =============================================================================
+// History
+// =============================================================================
+
+/// The most recent interactions, oldest first.
+///
+/// Only the newest entry can be pending: recording an interaction settles
+/// the previous one, since later events belong to the new one.
+#[derive(Debug)]
+pub struct History {
+    entries: VecDeque<Interaction>,
+    capacity: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(HISTORY_CAPACITY)
+    }
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add an interaction with a pending outcome. Returns the previous
+    /// interaction if this settled it.
+    pub fn record(&mut self, mut interaction: Interaction) -> Option<Interaction> {
+        let settled = self.settle(Outcome::Nothing).cloned();
+        interaction.outcome = Outcome::Pending;
+        self.entries.push_back(interaction);
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+        settled
+    }
+
+    /// Settle the pending interaction from a client event seen at `now`.
+    /// Returns the interaction if it was settled, by the event or, if its
+    /// window has already passed, as `Nothing`.
+    pub fn observe(&mut self, event: &ClientEvent, now: u64) -> Option<&Interaction> {
+        let outcome = match event {
+            ClientEvent::LootOpened => Outcome::LootOpened,
+            ClientEvent::UiErrorMessage { message } => Outcome::Error(message.clone()),
+            _ => return None,
+        };
+        let expired = self
+            .pending()
+            .is_some_and(|pending| now.saturating_sub(pending.time_ms) > OUTCOME_WINDOW_MS);
+        self.settle(if expired { Outcome::Nothing } else { outcome })
+    }
+
+    /// Settle the pending interaction as `Nothing` once its window has
+    /// passed at `now`. Returns the interaction if it was settled.
+    pub fn expire(&mut self, now: u64) -> Option<&Interaction> {
+        let pending = self.pending()?;
+        if now.saturating_sub(pending.time_ms) <= OUTCOME_WINDOW_MS {
+            return None;
+        }
+        self.settle(Outcome::Nothing)
+    }
+
+    /// Up to `count` interactions, newest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &Interaction> {
+        self.entries.iter().rev().take(count)
+    }
+
+    fn pending(&self) -> Option<&Interaction> {
+        self.entries
+            .back()
+            .filter(|interaction| interaction.outcome == Outcome::Pending)
+    }
+
+    /// Give the pending interaction, if any, its outcome
+    fn settle(&mut self, outcome: Outcome) -> Option<&Interaction> {
+        let pending = self
+            .entries
+            .back_mut()
+            .filter(|interaction| interaction.outcome == Outcome::Pending)?;
+        pending.outcome = outcome;
+        Some(pending)
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction(time_ms: u64, guid: u64) -> Interaction {
+        Interaction {
+            time_ms,
+            guid,
+            entry: 1731,
+            category: Some(Tier::GameObject),
+            distance: 2.5,
+            outcome: Outcome::Nothing,
+        }
+    }
+
+    fn too_far() -> ClientEvent {
+        ClientEvent::UiErrorMessage {
+            message: "You are too far away.".to_owned(),
+        }
+    }
+
+    fn outcomes(history: &History) -> Vec<Outcome> {
+        history
+            .recent(usize::MAX)
+            .map(|interaction| interaction.outcome.clone())
+            .collect()
+    }
+
+    // -------------------------------------------------------------------------
+    // Outcome tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_recorded_as_pending() {
+        let mut history = History::default();
+        assert_eq!(history.record(interaction(1000, 1)), None);
+        assert_eq!(outcomes(&history), [Outcome::Pending]);
+    }
+
+    #[test]
+    fn test_loot_and_error_settle_within_window() {
+        let mut history = History::default();
+        history.record(interaction(1000, 1));
+        let settled = history.observe(&ClientEvent::LootOpened, 1200).cloned();
+        assert_eq!(settled.map(|i| i.outcome), Some(Outcome::LootOpened));
+        // Later events do not change a settled outcome
+        assert_eq!(history.observe(&too_far(), 1300), None);
+
+        history.record(interaction(2000, 2));
+        history.observe(&too_far(), 2000 + OUTCOME_WINDOW_MS);
+        assert_eq!(
+            outcomes(&history),
+            [
+                Outcome::Error("You are too far away.".to_owned()),
+                Outcome::LootOpened
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_events_are_ignored() {
+        let mut history = History::default();
+        history.record(interaction(1000, 1));
+        for event in [
+            ClientEvent::PlayerTargetChanged,
+            ClientEvent::LootClosed,
+            ClientEvent::PlayerDead,
+        ] {
+            assert_eq!(history.observe(&event, 1100), None);
+        }
+        assert_eq!(outcomes(&history), [Outcome::Pending]);
+    }
+
+    #[test]
+    fn test_events_after_window_settle_as_nothing() {
+        let mut history = History::default();
+        history.record(interaction(1000, 1));
+        assert_eq!(history.expire(1000 + OUTCOME_WINDOW_MS), None);
+        let settled = history
+            .observe(&ClientEvent::LootOpened, 1001 + OUTCOME_WINDOW_MS)
+            .cloned();
+        assert_eq!(settled.map(|i| i.outcome), Some(Outcome::Nothing));
+        assert_eq!(outcomes(&history), [Outcome::Nothing]);
+        assert_eq!(history.expire(5000), None);
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut history = History::default();
+        history.record(interaction(1000, 1));
+        assert_eq!(history.expire(1500), None);
+        let settled = history.expire(1001 + OUTCOME_WINDOW_MS).cloned();
+        assert_eq!(settled.map(|i| i.guid), Some(1));
+        assert_eq!(outcomes(&history), [Outcome::Nothing]);
+    }
+
+    #[test]
+    fn test_record_settles_previous() {
+        let mut history = History::default();
+        history.record(interaction(1000, 1));
+        let settled = history.record(interaction(1100, 2));
+        assert_eq!(
+            settled.map(|i| (i.guid, i.outcome)),
+            Some((1, Outcome::Nothing))
+        );
+        history.observe(&ClientEvent::LootOpened, 1150);
+        assert_eq!(outcomes(&history), [Outcome::LootOpened, Outcome::Nothing]);
+    }
+
+    // -------------------------------------------------------------------------
+    // History tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut history = History::new(3);
+        for guid in 1..=5 {
+            history.record(interaction(guid * 10_000, guid));
+        }
+        assert_eq!(history.len(), 3);
+        let guids: Vec<u64> = history.recent(usize::MAX).map(|i| i.guid).collect();
+        assert_eq!(guids, [5, 4, 3]);
+        let guids: Vec<u64> = history.recent(2).map(|i| i.guid).collect();
+        assert_eq!(guids, [5, 4]);
+        assert_eq!(history.recent(0).count(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut entry = interaction(1000, 0x10);
+        entry.outcome = Outcome::Error("Inventory is full.".to_owned());
+        assert_eq!(
+            entry.to_string(),
+            "guid 0x0000000000000010, entry 1731, gameobject, 2.50 yd: error \"Inventory is full.\""
+        );
+        entry.category = None;
+        entry.outcome = Outcome::LootOpened;
+        assert_eq!(
+            entry.to_string(),
+            "guid 0x0000000000000010, entry 1731, unknown, 2.50 yd: loot opened"
+        );
+    }
+}
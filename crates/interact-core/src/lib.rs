@@ -0,0 +1,47 @@
+//! interact-core - Platform-independent part of interact-rs
+//!
+//! Types, candidate selection, configuration, the blacklist, slash command
+//! parsing, world snapshots, the frame tick scheduler, client event dispatch,
+//! the interaction history, the settings in effect and the script functions.
+//! Nothing here touches game memory or the Win32 API, so the crate builds
+//! and tests natively on any platform; the `interact` DLL crate wires it up
+//! to the running client, and `interact-sim` to world fixtures.
+
+// =============================================================================
+// Lints
+// =============================================================================
+
+// Enable comprehensive clippy lints for code quality
+#![warn(clippy::all)]
+#![warn(clippy::pedantic)]
+// Allow these specific patterns that are intentional in this codebase
+#![allow(clippy::missing_errors_doc)] // Errors are described by their types
+#![allow(clippy::missing_panics_doc)] // Panics are documented where relevant
+#![allow(clippy::missing_safety_doc)] // Lua API calls need a valid state from the caller
+#![allow(clippy::must_use_candidate)] // Many functions have side effects
+#![allow(clippy::cast_possible_truncation)] // Intentional u64 -> u32 casts for game values
+#![allow(clippy::cast_sign_loss)] // Lua numbers saturate when cast to entry IDs and counts
+#![allow(clippy::cast_precision_loss)] // Intentional f64 -> f32 conversions
+#![allow(clippy::unreadable_literal)] // Memory addresses match game documentation format
+#![allow(clippy::doc_markdown)] // Technical terms don't need backticks everywhere
+#![allow(clippy::non_std_lazy_statics)] // once_cell::Lazy, as in the DLL crate
+
+#[macro_use]
+pub mod log;
+pub mod commands;
+pub mod config;
+pub mod descriptors;
+pub mod diagnostics;
+pub mod errors;
+pub mod events;
+pub mod frame;
+pub mod game;
+pub mod history;
+pub mod lua;
+pub mod scheduler;
+pub mod script;
+pub mod selection;
+pub mod settings;
+pub mod snapshot;
+
+pub use errors::{ConfigError, SnapshotError};
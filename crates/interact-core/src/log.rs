@@ -0,0 +1,58 @@
+//! Debug log output for interact-core
+//!
+//! The core crate has no log file of its own. The DLL installs its writer
+//! with `set_writer` once `Logs\interact_debug.log` is open; until then, and
+//! in tests and `interact-sim`, messages are dropped. The trace flag lives
+//! here so that the script functions in `script` can toggle it.
+
+use once_cell::sync::OnceCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Where messages go, set once by the DLL
+static WRITER: OnceCell<fn(&str)> = OnceCell::new();
+
+/// Whether verbose trace messages are written (off by default)
+static TRACE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Send every message to `writer`. Only the first call has an effect.
+pub fn set_writer(writer: fn(&str)) {
+    let _ = WRITER.set(writer);
+}
+
+/// Write a message, if a writer has been installed
+pub fn write(message: &str) {
+    if let Some(writer) = WRITER.get() {
+        writer(message);
+    }
+}
+
+/// Enable or disable verbose trace messages
+pub fn set_trace(enabled: bool) {
+    TRACE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if trace messages are enabled
+#[inline]
+pub fn trace_enabled() -> bool {
+    TRACE_ENABLED.load(Ordering::Relaxed)
+}
+
+// =============================================================================
+// Logging Macros
+// =============================================================================
+
+/// Debug logging through the installed writer
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        $crate::log::write(&format!($($arg)*))
+    };
+}
+
+/// Verbose logging, only formatted and written when trace is enabled
+macro_rules! trace_log {
+    ($($arg:tt)*) => {
+        if $crate::log::trace_enabled() {
+            $crate::log::write(&format!("[TRACE] {}", format_args!($($arg)*)))
+        }
+    };
+}
@@ -0,0 +1,269 @@
+//! Lua C API surface used by the script functions
+//!
+//! The DLL implements `LuaApi` over the client's Lua 5.0 functions. Tests
+//! implement it over a host Lua build (`host::HostLua`), so the Lua-facing
+//! half of each script function can be called from real Lua snippets.
+
+use std::ffi::{c_char, c_int, c_void, CStr};
+
+/// Type tag returned by `type_of` for `nil` (Lua 5.0 `LUA_TNIL`)
+pub const LUA_TNIL: c_int = 0;
+
+/// Opaque Lua state pointer
+pub type LuaState = *mut c_void;
+
+/// The Lua C API functions the script functions call.
+///
+/// Method names follow the C API (`lua_gettop` is `gettop`, and so on).
+pub trait LuaApi {
+    /// Get the index of the top element in the stack
+    unsafe fn gettop(&self, l: LuaState) -> i32;
+
+    /// Set the stack top to the given index
+    unsafe fn settop(&self, l: LuaState, idx: i32);
+
+    /// Get the type of the value at the given index
+    unsafe fn type_of(&self, l: LuaState, idx: i32) -> i32;
+
+    /// Check if the value at index is a number
+    unsafe fn isnumber(&self, l: LuaState, idx: i32) -> bool;
+
+    /// Check if the value at index is a string (or a number)
+    unsafe fn isstring(&self, l: LuaState, idx: i32) -> bool;
+
+    /// Convert value at index to a number
+    unsafe fn tonumber(&self, l: LuaState, idx: i32) -> f64;
+
+    /// Convert value at index to a raw C string pointer, null if it is not
+    /// a string or number
+    unsafe fn tostring_raw(&self, l: LuaState, idx: i32) -> *const c_char;
+
+    /// Push a number onto the stack
+    unsafe fn pushnumber(&self, l: LuaState, n: f64);
+
+    /// Push a string onto the stack
+    unsafe fn pushstring(&self, l: LuaState, s: *const c_char);
+
+    /// Push nil onto the stack
+    unsafe fn pushnil(&self, l: LuaState);
+
+    /// Push a boolean onto the stack
+    unsafe fn pushboolean(&self, l: LuaState, b: bool);
+
+    /// Push a new empty table onto the stack
+    unsafe fn newtable(&self, l: LuaState);
+
+    /// Set `t[k] = v`, where `t` is at `idx`, `v` is on top of the stack and
+    /// `k` is just below it. Pops the key and value.
+    unsafe fn settable(&self, l: LuaState, idx: i32);
+
+    /// Raise a Lua error with a message.
+    ///
+    /// Does not return: Lua longjmps back to the caller of the script
+    /// function, skipping every Rust frame in between. Nothing that needs
+    /// dropping (lock guards, `CString`s) may be alive when this is called.
+    unsafe fn error(&self, l: LuaState, msg: *const c_char) -> !;
+
+    /// Pop n elements from the stack
+    #[inline]
+    unsafe fn pop(&self, l: LuaState, n: i32) {
+        self.settop(l, -n - 1);
+    }
+
+    /// Convert value at index to a string
+    /// Returns None if the value is not a string or is null
+    unsafe fn tostring(&self, l: LuaState, idx: i32) -> Option<&'static str> {
+        let ptr = self.tostring_raw(l, idx);
+        if ptr.is_null() {
+            return None;
+        }
+        CStr::from_ptr(ptr).to_str().ok()
+    }
+
+    /// Set a string-keyed field of the table on top of the stack to the value
+    /// pushed by `push_value`
+    unsafe fn setfield(&self, l: LuaState, key: &CStr, push_value: impl FnOnce()) {
+        self.pushstring(l, key.as_ptr());
+        push_value();
+        self.settable(l, -3);
+    }
+}
+
+// =============================================================================
+// Host Lua
+// =============================================================================
+
+/// `LuaApi` over the host Lua build from `interact-lua-host`
+#[cfg(test)]
+pub(crate) mod host {
+    use super::{LuaApi, LuaState};
+    use interact_lua_host::ffi;
+    use std::ffi::c_char;
+
+    pub struct HostLua;
+
+    impl LuaApi for HostLua {
+        unsafe fn gettop(&self, l: LuaState) -> i32 {
+            ffi::lua_gettop(l)
+        }
+
+        unsafe fn settop(&self, l: LuaState, idx: i32) {
+            ffi::lua_settop(l, idx);
+        }
+
+        unsafe fn type_of(&self, l: LuaState, idx: i32) -> i32 {
+            ffi::lua_type(l, idx)
+        }
+
+        unsafe fn isnumber(&self, l: LuaState, idx: i32) -> bool {
+            ffi::lua_isnumber(l, idx) != 0
+        }
+
+        unsafe fn isstring(&self, l: LuaState, idx: i32) -> bool {
+            ffi::lua_isstring(l, idx) != 0
+        }
+
+        unsafe fn tonumber(&self, l: LuaState, idx: i32) -> f64 {
+            ffi::lua_tonumber(l, idx)
+        }
+
+        unsafe fn tostring_raw(&self, l: LuaState, idx: i32) -> *const c_char {
+            ffi::lua_tolstring(l, idx, std::ptr::null_mut())
+        }
+
+        unsafe fn pushnumber(&self, l: LuaState, n: f64) {
+            ffi::lua_pushnumber(l, n);
+        }
+
+        unsafe fn pushstring(&self, l: LuaState, s: *const c_char) {
+            ffi::lua_pushstring(l, s);
+        }
+
+        unsafe fn pushnil(&self, l: LuaState) {
+            ffi::lua_pushnil(l);
+        }
+
+        unsafe fn pushboolean(&self, l: LuaState, b: bool) {
+            ffi::lua_pushboolean(l, i32::from(b));
+        }
+
+        unsafe fn newtable(&self, l: LuaState) {
+            ffi::lua_createtable(l, 0, 0);
+        }
+
+        unsafe fn settable(&self, l: LuaState, idx: i32) {
+            ffi::lua_settable(l, idx);
+        }
+
+        /// The client's `lua_error` takes the message directly; the host's
+        /// raises the value on top of the stack, so push it first
+        unsafe fn error(&self, l: LuaState, msg: *const c_char) -> ! {
+            ffi::lua_pushstring(l, msg);
+            ffi::lua_error(l);
+            unreachable!("lua_error returned")
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::host::HostLua;
+    use super::*;
+    use interact_lua_host::Lua;
+    use std::ffi::c_int;
+
+    unsafe extern "C" fn describe(l: LuaState) -> c_int {
+        let lua = HostLua;
+        let text = lua.tostring(l, 1).unwrap_or("<none>");
+        let message = std::ffi::CString::new(format!("{text}/{}", lua.gettop(l))).unwrap();
+        lua.pushstring(l, message.as_ptr());
+        1
+    }
+
+    unsafe extern "C" fn make_table(l: LuaState) -> c_int {
+        let lua = HostLua;
+        lua.newtable(l);
+        lua.setfield(l, c"answer", || lua.pushnumber(l, 42.0));
+        lua.setfield(l, c"ok", || lua.pushboolean(l, true));
+        1
+    }
+
+    unsafe extern "C" fn fail(l: LuaState) -> c_int {
+        HostLua.error(l, c"Usage: Fail()".as_ptr())
+    }
+
+    unsafe extern "C" fn pop_one(l: LuaState) -> c_int {
+        let lua = HostLua;
+        lua.pop(l, 1);
+        lua.gettop(l)
+    }
+
+    fn lua() -> Lua {
+        let lua = Lua::new();
+        lua.register(c"Describe", describe);
+        lua.register(c"MakeTable", make_table);
+        lua.register(c"Fail", fail);
+        lua.register(c"PopOne", pop_one);
+        lua
+    }
+
+    // -------------------------------------------------------------------------
+    // LuaApi tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_tostring_converts_numbers_and_rejects_tables() {
+        let lua = lua();
+        lua.exec(
+            r#"
+            assert(Describe("text") == "text/1")
+            assert(Describe(5) == "5/1")
+            assert(Describe({}) == "<none>/1")
+            assert(Describe() == "<none>/0")
+            "#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_setfield_builds_table() {
+        let lua = lua();
+        lua.exec(
+            r"
+            local t = MakeTable()
+            assert(t.answer == 42)
+            assert(t.ok == true)
+            ",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_error_raises_lua_error() {
+        let lua = lua();
+        assert_eq!(lua.exec("Fail()"), Err("Usage: Fail()".to_owned()));
+        lua.exec(
+            r#"
+            local ok, err = pcall(Fail)
+            assert(not ok and err == "Usage: Fail()")
+            "#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_pop_returns_remaining_values() {
+        let lua = lua();
+        lua.exec(
+            r"
+            local a, b = PopOne(1, 2, 3)
+            assert(a == 1 and b == 2)
+            ",
+        )
+        .unwrap();
+    }
+}
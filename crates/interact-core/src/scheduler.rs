@@ -0,0 +1,372 @@
+//! Task scheduler driven by the frame tick
+//!
+//! Lets the DLL act without being called from Lua: run something on the next
+//! frame, after a delay, or repeatedly. The DLL calls `take_due` from its
+//! frame tick hook, runs the tasks outside any borrow of the scheduler so
+//! they can schedule follow-ups, and hands repeating ones back with
+//! `requeue`. A task that panics is dropped and reported; the others keep
+//! running.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Identifies a scheduled task, for `Scheduler::cancel`
+pub type TaskId = u64;
+
+// =============================================================================
+// Tasks
+// =============================================================================
+
+/// When a task runs next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Due {
+    /// At the next tick, whatever the time
+    NextTick,
+    /// At the first tick at or after this time, in milliseconds
+    At(u64),
+}
+
+/// A scheduled closure
+pub struct Task {
+    id: TaskId,
+    name: &'static str,
+    due: Due,
+    /// Interval of a repeating task in milliseconds
+    every: Option<u64>,
+    run: Box<dyn FnMut()>,
+}
+
+impl Task {
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Name used in logs and error reports
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Run the task, catching a panic and returning its message
+    pub fn run(&mut self) -> Result<(), String> {
+        panic::catch_unwind(AssertUnwindSafe(|| (self.run)())).map_err(|e| panic_message(&*e))
+    }
+}
+
+impl std::fmt::Debug for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Task")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("due", &self.due)
+            .field("every", &self.every)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Text of a panic payload
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+// =============================================================================
+// Scheduler
+// =============================================================================
+
+/// Pending tasks, in the order they were scheduled
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    next_id: TaskId,
+    tasks: Vec<Task>,
+    /// Tasks handed out by `take_due` and not yet requeued
+    running: HashSet<TaskId>,
+    /// Running tasks cancelled while they ran
+    cancelled: HashSet<TaskId>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `task` once, at the next tick
+    pub fn next_tick(&mut self, name: &'static str, task: impl FnMut() + 'static) -> TaskId {
+        self.push(name, Due::NextTick, None, task)
+    }
+
+    /// Run `task` once, `delay` milliseconds after `now`
+    pub fn after(
+        &mut self,
+        name: &'static str,
+        now: u64,
+        delay: u64,
+        task: impl FnMut() + 'static,
+    ) -> TaskId {
+        self.push(name, Due::At(now.saturating_add(delay)), None, task)
+    }
+
+    /// Run `task` every `interval` milliseconds, starting `interval` after
+    /// `now`. An interval of 0 runs it on every tick.
+    pub fn every(
+        &mut self,
+        name: &'static str,
+        now: u64,
+        interval: u64,
+        task: impl FnMut() + 'static,
+    ) -> TaskId {
+        self.push(
+            name,
+            Due::At(now.saturating_add(interval)),
+            Some(interval),
+            task,
+        )
+    }
+
+    /// Stop a task from running again. Returns `false` if it is not
+    /// scheduled (it already ran, was cancelled or never existed).
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        if let Some(index) = self.tasks.iter().position(|task| task.id == id) {
+            self.tasks.remove(index);
+            true
+        } else if self.running.contains(&id) {
+            self.cancelled.insert(id)
+        } else {
+            false
+        }
+    }
+
+    /// Number of scheduled tasks, running ones included
+    pub fn len(&self) -> usize {
+        self.tasks.len() + self.running.len() - self.cancelled.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove and return the tasks due at `now`, in the order they were
+    /// scheduled. Repeating tasks must be handed back with `requeue`.
+    pub fn take_due(&mut self, now: u64) -> Vec<Task> {
+        let (due, pending): (Vec<Task>, Vec<Task>) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|task| match task.due {
+                Due::NextTick => true,
+                Due::At(at) => at <= now,
+            });
+        self.tasks = pending;
+        self.running.extend(due.iter().map(|task| task.id));
+        due
+    }
+
+    /// Hand back a task returned by `take_due` after running it. Repeating
+    /// tasks are scheduled again unless cancelled while they ran; one-shot
+    /// tasks are dropped.
+    pub fn requeue(&mut self, mut task: Task, now: u64) {
+        self.running.remove(&task.id);
+        if self.cancelled.remove(&task.id) {
+            return;
+        }
+        if let Some(every) = task.every {
+            task.due = Due::At(now.saturating_add(every));
+            self.tasks.push(task);
+        }
+    }
+
+    /// Forget a task returned by `take_due` that will not be requeued, such
+    /// as one that panicked
+    pub fn discard(&mut self, task: &Task) {
+        self.running.remove(&task.id);
+        self.cancelled.remove(&task.id);
+    }
+
+    fn push(
+        &mut self,
+        name: &'static str,
+        due: Due,
+        every: Option<u64>,
+        task: impl FnMut() + 'static,
+    ) -> TaskId {
+        self.next_id += 1;
+        self.tasks.push(Task {
+            id: self.next_id,
+            name,
+            due,
+            every,
+            run: Box::new(task),
+        });
+        self.next_id
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Names of the tasks that ran, in order
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn logger(log: &Log, name: &'static str) -> impl FnMut() + 'static {
+        let log = Rc::clone(log);
+        move || log.borrow_mut().push(name)
+    }
+
+    /// One frame tick: run the due tasks and requeue them, as the DLL does.
+    /// Returns the names of tasks that panicked.
+    fn tick(scheduler: &Rc<RefCell<Scheduler>>, now: u64) -> Vec<&'static str> {
+        let due = scheduler.borrow_mut().take_due(now);
+        let mut panicked = Vec::new();
+        for mut task in due {
+            if task.run().is_ok() {
+                scheduler.borrow_mut().requeue(task, now);
+            } else {
+                panicked.push(task.name());
+                scheduler.borrow_mut().discard(&task);
+            }
+        }
+        panicked
+    }
+
+    fn setup() -> (Rc<RefCell<Scheduler>>, Log) {
+        (Rc::default(), Log::default())
+    }
+
+    // -------------------------------------------------------------------------
+    // Scheduling tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_next_tick_runs_once() {
+        let (scheduler, log) = setup();
+        scheduler.borrow_mut().next_tick("a", logger(&log, "a"));
+        assert_eq!(scheduler.borrow().len(), 1);
+        tick(&scheduler, 0);
+        tick(&scheduler, 1);
+        assert_eq!(*log.borrow(), ["a"]);
+        assert!(scheduler.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_after_waits_for_the_delay() {
+        let (scheduler, log) = setup();
+        scheduler
+            .borrow_mut()
+            .after("late", 1000, 250, logger(&log, "late"));
+        scheduler
+            .borrow_mut()
+            .after("early", 1000, 100, logger(&log, "early"));
+        tick(&scheduler, 1099);
+        assert!(log.borrow().is_empty());
+        tick(&scheduler, 1100);
+        assert_eq!(*log.borrow(), ["early"]);
+        // A late tick runs everything overdue, in scheduling order
+        scheduler
+            .borrow_mut()
+            .after("later", 1100, 50, logger(&log, "later"));
+        tick(&scheduler, 2000);
+        assert_eq!(*log.borrow(), ["early", "late", "later"]);
+    }
+
+    #[test]
+    fn test_every_repeats_from_the_last_run() {
+        let (scheduler, log) = setup();
+        scheduler
+            .borrow_mut()
+            .every("poll", 0, 100, logger(&log, "poll"));
+        for now in [50, 100, 150, 199, 200, 450] {
+            tick(&scheduler, now);
+        }
+        // Ran at 100 and 200; 450 is late and runs once, not twice
+        assert_eq!(log.borrow().len(), 3);
+        assert_eq!(scheduler.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let (scheduler, log) = setup();
+        let id = scheduler
+            .borrow_mut()
+            .every("poll", 0, 10, logger(&log, "poll"));
+        tick(&scheduler, 10);
+        assert!(scheduler.borrow_mut().cancel(id));
+        assert!(!scheduler.borrow_mut().cancel(id));
+        tick(&scheduler, 20);
+        assert_eq!(log.borrow().len(), 1);
+        assert!(scheduler.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_task_can_schedule_and_cancel_while_running() {
+        let (scheduler, log) = setup();
+        let follow_up = {
+            let scheduler = Rc::clone(&scheduler);
+            let log = Rc::clone(&log);
+            move || {
+                log.borrow_mut().push("first");
+                scheduler
+                    .borrow_mut()
+                    .next_tick("second", logger(&log, "second"));
+            }
+        };
+        scheduler.borrow_mut().next_tick("first", follow_up);
+        tick(&scheduler, 0);
+        assert_eq!(*log.borrow(), ["first"]);
+        tick(&scheduler, 0);
+        assert_eq!(*log.borrow(), ["first", "second"]);
+
+        // A repeating task that cancels itself is not requeued
+        let id = Rc::new(RefCell::new(0));
+        let stop = {
+            let scheduler = Rc::clone(&scheduler);
+            let id = Rc::clone(&id);
+            move || {
+                let id = *id.borrow();
+                assert!(scheduler.borrow_mut().cancel(id));
+            }
+        };
+        *id.borrow_mut() = scheduler.borrow_mut().every("stop", 0, 0, stop);
+        tick(&scheduler, 0);
+        assert!(scheduler.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_panicking_task_is_isolated() {
+        let (scheduler, log) = setup();
+        scheduler
+            .borrow_mut()
+            .every("broken", 0, 0, || panic!("boom"));
+        scheduler
+            .borrow_mut()
+            .every("fine", 0, 0, logger(&log, "fine"));
+        assert_eq!(tick(&scheduler, 0), ["broken"]);
+        assert_eq!(tick(&scheduler, 1), Vec::<&str>::new());
+        assert_eq!(*log.borrow(), ["fine", "fine"]);
+        assert_eq!(scheduler.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let mut scheduler = Scheduler::new();
+        scheduler.next_tick("str", || panic!("static message"));
+        scheduler.next_tick("string", || panic!("formatted {}", 42));
+        let results: Vec<_> = scheduler.take_due(0).iter_mut().map(Task::run).collect();
+        assert_eq!(
+            results,
+            [
+                Err("static message".to_owned()),
+                Err("formatted 42".to_owned())
+            ]
+        );
+    }
+}
@@ -0,0 +1,1166 @@
+//! The script functions, generic over `LuaApi`
+//!
+//! Every `Interact*` function that needs nothing from the client lives here
+//! whole: it checks its arguments, changes `settings` and pushes its
+//! results. The DLL's `Script_*` functions forward to these, and tests
+//! register the same functions with a host Lua. Functions that need the
+//! client (`nearest`, `command`, `get_history`) take that part as a closure;
+//! the rest of the DLL's functions use the argument and result helpers.
+//!
+//! Usage errors are raised with `LuaApi::error`, which does not return.
+//! Nothing here holds a value that needs dropping when it is called, and
+//! closures passed in must not own one either.
+
+use crate::commands::{self, Command};
+use crate::config::{self, DEFAULT_PRIORITY};
+use crate::diagnostics::Report;
+use crate::errors::{ConfigError, SnapshotError};
+use crate::game::{self, NameFilter};
+use crate::history::{Interaction, Outcome};
+use crate::lua::{LuaApi, LuaState, LUA_TNIL};
+use crate::selection::{FacingConfig, LineOfSightConfig, RangeConfig, RangeModel};
+use crate::settings::{FACING, LINE_OF_SIGHT, NAME_FILTER, OBJECT_RADII, PRIORITY, RANGE};
+use std::ffi::{c_int, CStr, CString};
+use std::path::PathBuf;
+use std::sync::PoisonError;
+
+// =============================================================================
+// Error Messages (null-terminated for C)
+// =============================================================================
+
+pub const ERR_USAGE: &CStr = c"Usage: InteractNearest(autoloot)";
+pub const ERR_USAGE_NAME_FILTERS: &CStr = c"Usage: InteractSetNameFilters(include, exclude)";
+pub const ERR_USAGE_RANGE: &CStr =
+    c"Usage: InteractSetRange(distance, maxHeight, unitRadius, objectRadius)";
+pub const ERR_USAGE_OBJECT_RADIUS: &CStr = c"Usage: InteractSetObjectRadius(entryId, radius)";
+pub const ERR_USAGE_FACING: &CStr = c"Usage: InteractSetFacing(cone, weight)";
+pub const ERR_USAGE_LINE_OF_SIGHT: &CStr = c"Usage: InteractSetLineOfSight(enabled, maxChecks)";
+pub const ERR_USAGE_PRIORITY: &CStr = c"Usage: InteractSetPriority(order)";
+pub const ERR_USAGE_BLACKLIST: &CStr = c"Usage: InteractSetBlacklist(ids)";
+pub const ERR_USAGE_TRACE: &CStr = c"Usage: InteractSetTrace(enabled)";
+pub const ERR_USAGE_COMMAND: &CStr = c"Usage: InteractCommand(msg)";
+pub const ERR_USAGE_HISTORY: &CStr = c"Usage: InteractGetHistory(n)";
+
+// =============================================================================
+// Arguments
+// =============================================================================
+
+/// `InteractNearest(autoloot)`: the autoloot flag
+pub unsafe fn autoloot_arg<L: LuaApi>(lua: &L, l: LuaState) -> i32 {
+    if !lua.isnumber(l, 1) {
+        lua.error(l, ERR_USAGE.as_ptr());
+    }
+    lua.tonumber(l, 1) as i32
+}
+
+/// `InteractSetNameFilters(include, exclude)`: nil or missing lists are empty
+pub unsafe fn name_filter_args<L: LuaApi>(lua: &L, l: LuaState) -> NameFilter {
+    let mut lists = ["", ""];
+    for (idx, list) in (1..).zip(lists.iter_mut()) {
+        if lua.isstring(l, idx) {
+            *list = lua.tostring(l, idx).unwrap_or("");
+        } else if lua.type_of(l, idx) > LUA_TNIL {
+            lua.error(l, ERR_USAGE_NAME_FILTERS.as_ptr());
+        }
+    }
+    NameFilter::parse(lists[0], lists[1])
+}
+
+/// `InteractSetRange(distance, maxHeight, unitRadius, objectRadius)`
+pub unsafe fn range_args<L: LuaApi>(lua: &L, l: LuaState) -> RangeConfig {
+    if !lua.isnumber(l, 1) {
+        lua.error(l, ERR_USAGE_RANGE.as_ptr());
+    }
+
+    // Optional numeric arguments default to 0
+    let mut optional = [0.0_f32; 3];
+    for (idx, value) in (2..).zip(optional.iter_mut()) {
+        if lua.isnumber(l, idx) {
+            *value = (lua.tonumber(l, idx) as f32).max(0.0);
+        } else if lua.type_of(l, idx) > LUA_TNIL {
+            lua.error(l, ERR_USAGE_RANGE.as_ptr());
+        }
+    }
+    let [max_height, unit_radius, gameobject_radius] = optional;
+
+    RangeConfig {
+        max_distance: (lua.tonumber(l, 1) as f32).max(0.0),
+        model: if max_height > 0.0 {
+            RangeModel::Cylindrical { max_height }
+        } else {
+            RangeModel::Spherical
+        },
+        unit_radius,
+        gameobject_radius,
+    }
+}
+
+/// `InteractSetObjectRadius(entryId, radius)`: the entry and its radius
+/// (0 removes it), or `None` when entryId is nil and every entry is cleared
+pub unsafe fn object_radius_args<L: LuaApi>(lua: &L, l: LuaState) -> Option<(u32, f32)> {
+    if lua.type_of(l, 1) == LUA_TNIL {
+        return None;
+    }
+
+    let has_radius = lua.isnumber(l, 2);
+    if !lua.isnumber(l, 1) || (!has_radius && lua.type_of(l, 2) > LUA_TNIL) {
+        lua.error(l, ERR_USAGE_OBJECT_RADIUS.as_ptr());
+    }
+
+    let radius = if has_radius {
+        lua.tonumber(l, 2) as f32
+    } else {
+        0.0
+    };
+    Some((lua.tonumber(l, 1) as u32, radius))
+}
+
+/// `InteractSetFacing(cone, weight)`
+pub unsafe fn facing_args<L: LuaApi>(lua: &L, l: LuaState) -> FacingConfig {
+    if !lua.isnumber(l, 1) || !lua.isnumber(l, 2) {
+        lua.error(l, ERR_USAGE_FACING.as_ptr());
+    }
+    FacingConfig::from_degrees(lua.tonumber(l, 1) as f32, lua.tonumber(l, 2) as f32)
+}
+
+/// `InteractSetLineOfSight(enabled, maxChecks)`
+pub unsafe fn line_of_sight_args<L: LuaApi>(lua: &L, l: LuaState) -> LineOfSightConfig {
+    if !lua.isnumber(l, 1) {
+        lua.error(l, ERR_USAGE_LINE_OF_SIGHT.as_ptr());
+    }
+    let max_checks = if lua.isnumber(l, 2) {
+        (lua.tonumber(l, 2) as u32).max(1)
+    } else if lua.type_of(l, 2) > LUA_TNIL {
+        lua.error(l, ERR_USAGE_LINE_OF_SIGHT.as_ptr());
+    } else {
+        LineOfSightConfig::DEFAULT.max_checks
+    };
+
+    LineOfSightConfig {
+        enabled: lua.tonumber(l, 1) != 0.0,
+        max_checks,
+    }
+}
+
+/// `InteractSetTrace(enabled)`
+pub unsafe fn trace_arg<L: LuaApi>(lua: &L, l: LuaState) -> bool {
+    if !lua.isnumber(l, 1) {
+        lua.error(l, ERR_USAGE_TRACE.as_ptr());
+    }
+    lua.tonumber(l, 1) != 0.0
+}
+
+/// `InteractGetHistory(n)`: how many entries to return, all if nil or missing
+pub unsafe fn history_count_arg<L: LuaApi>(lua: &L, l: LuaState) -> usize {
+    if lua.isnumber(l, 1) {
+        lua.tonumber(l, 1) as usize
+    } else if lua.type_of(l, 1) > LUA_TNIL {
+        lua.error(l, ERR_USAGE_HISTORY.as_ptr());
+    } else {
+        usize::MAX
+    }
+}
+
+/// Read an optional string argument; `None` if it is nil or missing
+pub unsafe fn optional_string<L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    idx: i32,
+    usage: &CStr,
+) -> Option<&'static str> {
+    if lua.isstring(l, idx) {
+        Some(lua.tostring(l, idx).unwrap_or(""))
+    } else if lua.type_of(l, idx) > LUA_TNIL {
+        lua.error(l, usage.as_ptr())
+    } else {
+        None
+    }
+}
+
+// =============================================================================
+// Results
+// =============================================================================
+
+/// Return `true` to Lua, or `nil` and the error message
+pub unsafe fn push_result<L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    result: Result<(), ConfigError>,
+) -> c_int {
+    match result {
+        Ok(()) => {
+            lua.pushboolean(l, true);
+            1
+        }
+        Err(e) => {
+            let message = CString::new(e.to_string()).unwrap_or_default();
+            lua.pushnil(l);
+            lua.pushstring(l, message.as_ptr());
+            2
+        }
+    }
+}
+
+/// Return the path of a written file to Lua, or `nil` and the error message
+pub unsafe fn push_path<L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    result: &Result<PathBuf, SnapshotError>,
+) -> c_int {
+    match result {
+        Ok(path) => {
+            let path = CString::new(path.display().to_string()).unwrap_or_default();
+            lua.pushstring(l, path.as_ptr());
+            1
+        }
+        Err(e) => {
+            let message = CString::new(e.to_string()).unwrap_or_default();
+            lua.pushnil(l);
+            lua.pushstring(l, message.as_ptr());
+            2
+        }
+    }
+}
+
+/// Return lines of output to Lua as one "\n"-separated string
+pub unsafe fn push_lines<L: LuaApi>(lua: &L, l: LuaState, lines: &[String]) -> c_int {
+    let output = CString::new(lines.join("\n")).unwrap_or_default();
+    lua.pushstring(l, output.as_ptr());
+    1
+}
+
+/// Return the range of `InteractGetRange`, as the four arguments
+/// `InteractSetRange` takes. maxHeight is 0 for 3D distance.
+pub unsafe fn push_range<L: LuaApi>(lua: &L, l: LuaState, range: &RangeConfig) -> c_int {
+    let max_height = match range.model {
+        RangeModel::Spherical => 0.0,
+        RangeModel::Cylindrical { max_height } => max_height,
+    };
+    for value in [
+        range.max_distance,
+        max_height,
+        range.unit_radius,
+        range.gameobject_radius,
+    ] {
+        lua.pushnumber(l, f64::from(value));
+    }
+    4
+}
+
+/// Return the version string and capability table of `InteractGetVersion`
+pub unsafe fn push_version<'a, L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    version: &str,
+    build: Option<u32>,
+    offsets: &str,
+    functions: impl IntoIterator<Item = &'a CStr>,
+    features: &[&CStr],
+) -> c_int {
+    let version = CString::new(version).unwrap_or_default();
+    let offsets = CString::new(offsets).unwrap_or_default();
+
+    lua.pushstring(l, version.as_ptr());
+
+    lua.newtable(l);
+    lua.setfield(l, c"version", || lua.pushstring(l, version.as_ptr()));
+    if let Some(build) = build {
+        lua.setfield(l, c"build", || lua.pushnumber(l, f64::from(build)));
+    }
+    lua.setfield(l, c"offsets", || lua.pushstring(l, offsets.as_ptr()));
+    lua.setfield(l, c"functions", || {
+        lua.newtable(l);
+        for name in functions {
+            lua.setfield(l, name, || lua.pushboolean(l, true));
+        }
+    });
+    lua.setfield(l, c"features", || {
+        lua.newtable(l);
+        for name in features {
+            lua.setfield(l, name, || lua.pushboolean(l, true));
+        }
+    });
+
+    2
+}
+
+/// Return the report table of `InteractDiagnostics`
+pub unsafe fn push_report<L: LuaApi>(lua: &L, l: LuaState, report: &Report) -> c_int {
+    // Keep the strings alive until the table has been built
+    let cstring = |text: &str| CString::new(text).unwrap_or_default();
+    let version = cstring(report.version);
+    let object_manager = cstring(&format!("{:#010x}", report.object_manager));
+    let player_guid = cstring(&format!("{:#018x}", report.player_guid));
+    let player_pointer = report
+        .player_pointer
+        .map(|pointer| cstring(&format!("{pointer:#010x}")));
+    let last_error = report.last_error.as_deref().map(cstring);
+    let config_path = cstring(&report.config_path);
+    let config_status = cstring(match &report.config_status {
+        None => "not loaded",
+        Some(Ok(())) => "ok",
+        Some(Err(e)) => e,
+    });
+    let lines: Vec<CString> = report.lines().iter().map(|line| cstring(line)).collect();
+
+    let push_bool = |key: &CStr, value: bool| {
+        lua.setfield(l, key, || lua.pushboolean(l, value));
+    };
+    let push_string = |key: &CStr, value: &CString| {
+        lua.setfield(l, key, || lua.pushstring(l, value.as_ptr()));
+    };
+    let push_number = |key: &CStr, value: f64| {
+        lua.setfield(l, key, || lua.pushnumber(l, value));
+    };
+
+    lua.newtable(l);
+    push_string(c"version", &version);
+    push_bool(c"hooksInitialized", report.hooks_initialized);
+    push_bool(c"bootstrapHook", report.bootstrap_hook_enabled);
+    push_bool(c"scriptHook", report.script_hook_enabled);
+    push_bool(c"frameHook", report.frame_hook_enabled);
+    push_bool(c"eventHook", report.event_hook_enabled);
+    push_number(c"scheduledTasks", report.scheduled_tasks as f64);
+    push_number(c"eventHandlers", report.event_handlers as f64);
+    push_bool(c"luaInitialized", report.lua_initialized);
+    if let Some(build) = report.client_build {
+        push_number(c"clientBuild", f64::from(build));
+    }
+    push_number(c"expectedBuild", f64::from(report.expected_build));
+    push_bool(c"buildMatches", report.build_matches());
+    push_bool(c"inWorld", report.world.in_world);
+    push_bool(c"canInteract", report.world.can_interact());
+    push_string(c"objectManager", &object_manager);
+    push_number(c"objectCount", report.objects_walked as f64);
+    push_string(c"playerGuid", &player_guid);
+    if let Some(pointer) = &player_pointer {
+        push_string(c"playerPointer", pointer);
+    }
+    if let Some(error) = &last_error {
+        push_string(c"lastError", error);
+    }
+    push_string(c"configPath", &config_path);
+    push_bool(c"configFound", report.config_found);
+    push_string(c"configStatus", &config_status);
+    lua.setfield(l, c"lines", || {
+        lua.newtable(l);
+        for (i, line) in (1..).zip(&lines) {
+            lua.pushnumber(l, f64::from(i));
+            lua.pushstring(l, line.as_ptr());
+            lua.settable(l, -3);
+        }
+    });
+
+    1
+}
+
+/// Return the interactions of `InteractGetHistory`, newest first, as an
+/// array of tables
+pub unsafe fn push_history<'a, L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    interactions: impl IntoIterator<Item = &'a Interaction>,
+) -> c_int {
+    let cstring = |text: &str| CString::new(text).unwrap_or_default();
+
+    lua.newtable(l);
+    for (i, interaction) in (1..).zip(interactions) {
+        let guid = cstring(&format!("{:#018x}", interaction.guid));
+        let category = interaction.category.map(|tier| cstring(tier.name()));
+        let outcome = cstring(interaction.outcome.name());
+        let message = match &interaction.outcome {
+            Outcome::Error(message) => Some(cstring(message)),
+            _ => None,
+        };
+
+        lua.pushnumber(l, f64::from(i));
+        lua.newtable(l);
+        lua.setfield(l, c"time", || {
+            lua.pushnumber(l, interaction.time_ms as f64 / 1000.0);
+        });
+        lua.setfield(l, c"guid", || lua.pushstring(l, guid.as_ptr()));
+        lua.setfield(l, c"entry", || {
+            lua.pushnumber(l, f64::from(interaction.entry));
+        });
+        if let Some(category) = &category {
+            lua.setfield(l, c"category", || lua.pushstring(l, category.as_ptr()));
+        }
+        lua.setfield(l, c"distance", || {
+            lua.pushnumber(l, f64::from(interaction.distance));
+        });
+        lua.setfield(l, c"outcome", || lua.pushstring(l, outcome.as_ptr()));
+        if let Some(message) = &message {
+            lua.setfield(l, c"message", || lua.pushstring(l, message.as_ptr()));
+        }
+        lua.settable(l, -3);
+    }
+
+    1
+}
+
+// =============================================================================
+// Functions
+// =============================================================================
+
+/// `InteractNearest(autoloot)`: pass the autoloot flag to `interact` and
+/// return its result count. The DLL returns 1 after interacting, like the
+/// original C version, which hands the autoloot argument back to Lua.
+pub unsafe fn nearest<L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    interact: impl FnOnce(i32) -> c_int,
+) -> c_int {
+    interact(autoloot_arg(lua, l))
+}
+
+/// `InteractSetNameFilters(include, exclude)`. Returns no values.
+pub unsafe fn set_name_filters<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let filter = name_filter_args(lua, l);
+    debug_log!("Name filters set: {:?}", filter);
+    *NAME_FILTER.write().unwrap_or_else(PoisonError::into_inner) = filter;
+    0
+}
+
+/// `InteractSetRange(distance, maxHeight, unitRadius, objectRadius)`.
+/// Returns no values.
+pub unsafe fn set_range<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let range = range_args(lua, l);
+    debug_log!("Range set: {:?}", range);
+    *RANGE.write().unwrap_or_else(PoisonError::into_inner) = range;
+    0
+}
+
+/// `InteractGetRange()`: the four `InteractSetRange` arguments
+pub unsafe fn get_range<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let range = *RANGE.read().unwrap_or_else(PoisonError::into_inner);
+    push_range(lua, l, &range)
+}
+
+/// `InteractSetObjectRadius(entryId, radius)`. Returns no values.
+pub unsafe fn set_object_radius<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let args = object_radius_args(lua, l);
+    let mut radii = OBJECT_RADII.write().unwrap_or_else(PoisonError::into_inner);
+
+    let Some((entry_id, radius)) = args else {
+        radii.clear();
+        debug_log!("Object radii cleared");
+        return 0;
+    };
+    radii.set(entry_id, radius);
+    debug_log!(
+        "Object radius for entry {} set to {:.1} ({} configured)",
+        entry_id,
+        radius,
+        radii.len()
+    );
+    0
+}
+
+/// `InteractSetFacing(cone, weight)`. Returns no values.
+pub unsafe fn set_facing<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let facing = facing_args(lua, l);
+    debug_log!("Facing set: {:?}", facing);
+    *FACING.write().unwrap_or_else(PoisonError::into_inner) = facing;
+    0
+}
+
+/// `InteractSetLineOfSight(enabled, maxChecks)`. Returns no values.
+pub unsafe fn set_line_of_sight<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let los = line_of_sight_args(lua, l);
+    debug_log!("Line of sight set: {:?}", los);
+    *LINE_OF_SIGHT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = los;
+    0
+}
+
+/// `InteractSetPriority(order)`: `true`, or `nil` and an error message
+pub unsafe fn set_priority<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let result = match optional_string(lua, l, 1, ERR_USAGE_PRIORITY) {
+        Some(text) => config::parse_priority(text),
+        None => Ok(DEFAULT_PRIORITY.to_vec()),
+    };
+    let result = result.map(|priority| {
+        debug_log!("Priority set: {:?}", priority);
+        *PRIORITY.write().unwrap_or_else(PoisonError::into_inner) = priority;
+    });
+    push_result(lua, l, result)
+}
+
+/// `InteractSetBlacklist(ids)`: `true`, or `nil` and an error message
+pub unsafe fn set_blacklist<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let result = match optional_string(lua, l, 1, ERR_USAGE_BLACKLIST) {
+        Some(text) => config::parse_id_list(text),
+        None => Ok(config::DEFAULT_BLACKLIST.to_vec()),
+    };
+    let result = result.map(|ids| {
+        debug_log!("Blacklist set: {:?}", ids);
+        game::set_blacklist(&ids);
+    });
+    push_result(lua, l, result)
+}
+
+/// `InteractSetTrace(enabled)`. Returns no values.
+pub unsafe fn set_trace<L: LuaApi>(lua: &L, l: LuaState) -> c_int {
+    let enabled = trace_arg(lua, l);
+    crate::log::set_trace(enabled);
+    debug_log!(
+        "Trace logging {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    0
+}
+
+/// `InteractCommand(msg)`: the output of the command, one string. Commands
+/// that need the client are run by `client`, see `commands::run`.
+pub unsafe fn command<L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    client: impl FnOnce(Command) -> Vec<String>,
+) -> c_int {
+    let input = optional_string(lua, l, 1, ERR_USAGE_COMMAND).unwrap_or("");
+    let lines = match Command::parse(input) {
+        Ok(command) => commands::run(command, client),
+        Err(e) => vec![e],
+    };
+    trace_log!("/interact {}: {:?}", input, lines);
+    push_lines(lua, l, &lines)
+}
+
+/// `InteractGetHistory(n)`: the interactions `recent(n)` returns, newest
+/// first, as an array of tables
+pub unsafe fn get_history<L: LuaApi>(
+    lua: &L,
+    l: LuaState,
+    recent: impl FnOnce(usize) -> Vec<Interaction>,
+) -> c_int {
+    let count = history_count_arg(lua, l);
+    push_history(lua, l, &recent(count))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::option_option)] // None: not called, Some(None): radii cleared
+    #![allow(clippy::float_cmp)] // Exact float comparisons are intentional in these tests
+
+    use super::*;
+    use crate::config::Tier;
+    use crate::game::WorldState;
+    use crate::lua::host::HostLua;
+    use crate::selection::{FacingConfig, LineOfSightConfig, RangeConfig, RangeModel};
+    use interact_lua_host::Lua;
+    use std::cell::{Cell, RefCell};
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Serializes the tests that change the global settings
+    static SETTINGS: Mutex<()> = Mutex::new(());
+
+    fn lock_settings() -> MutexGuard<'static, ()> {
+        SETTINGS.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // What the client closures were called with, per test thread
+    thread_local! {
+        static AUTOLOOT: Cell<Option<i32>> = const { Cell::new(None) };
+        static CLIENT_COMMAND: RefCell<Option<Command>> = const { RefCell::new(None) };
+    }
+
+    unsafe extern "C" fn nearest_interacts(l: LuaState) -> c_int {
+        nearest(&HostLua, l, |autoloot| {
+            AUTOLOOT.set(Some(autoloot));
+            1
+        })
+    }
+
+    unsafe extern "C" fn nearest_finds_nothing(l: LuaState) -> c_int {
+        nearest(&HostLua, l, |autoloot| {
+            AUTOLOOT.set(Some(autoloot));
+            0
+        })
+    }
+
+    unsafe extern "C" fn set_name_filters(l: LuaState) -> c_int {
+        super::set_name_filters(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_range(l: LuaState) -> c_int {
+        super::set_range(&HostLua, l)
+    }
+
+    unsafe extern "C" fn get_range(l: LuaState) -> c_int {
+        super::get_range(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_object_radius(l: LuaState) -> c_int {
+        super::set_object_radius(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_facing(l: LuaState) -> c_int {
+        super::set_facing(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_line_of_sight(l: LuaState) -> c_int {
+        super::set_line_of_sight(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_priority(l: LuaState) -> c_int {
+        super::set_priority(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_blacklist(l: LuaState) -> c_int {
+        super::set_blacklist(&HostLua, l)
+    }
+
+    unsafe extern "C" fn set_trace(l: LuaState) -> c_int {
+        super::set_trace(&HostLua, l)
+    }
+
+    unsafe extern "C" fn command(l: LuaState) -> c_int {
+        super::command(&HostLua, l, |command| {
+            CLIENT_COMMAND.with_borrow_mut(|slot| *slot = Some(command));
+            vec!["from the client".to_owned()]
+        })
+    }
+
+    unsafe extern "C" fn get_version(l: LuaState) -> c_int {
+        push_version(
+            &HostLua,
+            l,
+            "1.2.1",
+            Some(5875),
+            "1.12.1.5875",
+            [c"InteractNearest", c"InteractGetVersion"],
+            &[c"config"],
+        )
+    }
+
+    unsafe extern "C" fn get_version_unknown_build(l: LuaState) -> c_int {
+        push_version(&HostLua, l, "1.2.1", None, "1.12.1.5875", [], &[])
+    }
+
+    unsafe extern "C" fn diagnostics(l: LuaState) -> c_int {
+        let report = Report {
+            version: "1.2.1",
+            hooks_initialized: true,
+            bootstrap_hook_enabled: true,
+            script_hook_enabled: true,
+            frame_hook_enabled: false,
+            event_hook_enabled: true,
+            scheduled_tasks: 3,
+            event_handlers: 4,
+            lua_initialized: true,
+            client_build: Some(5875),
+            expected_build: 5875,
+            world: WorldState {
+                in_world: true,
+                ..WorldState::default()
+            },
+            object_manager: 0x1234_5678,
+            objects_walked: 42,
+            player_guid: 0x10,
+            player_pointer: None,
+            last_error: Some("Failed to enable hook".to_owned()),
+            config_path: "interact.toml".to_owned(),
+            config_found: false,
+            config_status: Some(Ok(())),
+        };
+        push_report(&HostLua, l, &report)
+    }
+
+    unsafe extern "C" fn capture_snapshot(l: LuaState) -> c_int {
+        push_path(
+            &HostLua,
+            l,
+            &Ok(PathBuf::from("Logs\\interact_snapshot_1.json")),
+        )
+    }
+
+    unsafe extern "C" fn capture_snapshot_not_in_world(l: LuaState) -> c_int {
+        push_path(&HostLua, l, &Err(SnapshotError::NotInWorld))
+    }
+
+    unsafe extern "C" fn get_history(l: LuaState) -> c_int {
+        super::get_history(&HostLua, l, |count| {
+            let history = [
+                Interaction {
+                    time_ms: 12_500,
+                    guid: 0x10,
+                    entry: 1731,
+                    category: Some(config::Tier::GameObject),
+                    distance: 2.5,
+                    outcome: Outcome::Error("You are too far away.".to_owned()),
+                },
+                Interaction {
+                    time_ms: 10_000,
+                    guid: 0x20,
+                    entry: 0,
+                    category: None,
+                    distance: 1.0,
+                    outcome: Outcome::LootOpened,
+                },
+            ];
+            history.into_iter().take(count).collect()
+        })
+    }
+
+    /// A host Lua with the script functions registered under the DLL's
+    /// names. Functions that need the client get a stand-in for that part.
+    fn lua() -> Lua {
+        let lua = Lua::new();
+        lua.register(c"InteractNearest", nearest_interacts);
+        lua.register(c"InteractNearestFindsNothing", nearest_finds_nothing);
+        lua.register(c"InteractSetRange", set_range);
+        lua.register(c"InteractGetRange", get_range);
+        lua.register(c"InteractSetObjectRadius", set_object_radius);
+        lua.register(c"InteractSetFacing", set_facing);
+        lua.register(c"InteractSetLineOfSight", set_line_of_sight);
+        lua.register(c"InteractSetNameFilters", set_name_filters);
+        lua.register(c"InteractSetTrace", set_trace);
+        lua.register(c"InteractSetPriority", set_priority);
+        lua.register(c"InteractSetBlacklist", set_blacklist);
+        lua.register(c"InteractCommand", command);
+        lua.register(c"InteractGetVersion", get_version);
+        lua.register(c"InteractGetVersionUnknownBuild", get_version_unknown_build);
+        lua.register(c"InteractDiagnostics", diagnostics);
+        lua.register(c"InteractCaptureSnapshot", capture_snapshot);
+        lua.register(c"InteractGetHistory", get_history);
+        lua.register(
+            c"InteractCaptureSnapshotNotInWorld",
+            capture_snapshot_not_in_world,
+        );
+        lua
+    }
+
+    /// Run a snippet that is expected to raise exactly `usage`
+    fn assert_usage(lua: &Lua, code: &str, usage: &CStr) {
+        let err = lua.exec(code).expect_err(code);
+        assert_eq!(err, usage.to_str().unwrap(), "{code}");
+    }
+
+    // -------------------------------------------------------------------------
+    // Error message tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_error_message_is_valid_cstr() {
+        // CStr is guaranteed to be null-terminated, so we just verify it's valid
+        assert!(!ERR_USAGE.to_bytes().is_empty());
+        assert!(ERR_USAGE.to_str().is_ok());
+        assert!(ERR_USAGE_NAME_FILTERS.to_str().is_ok());
+        assert!(ERR_USAGE_RANGE.to_str().is_ok());
+        assert!(ERR_USAGE_OBJECT_RADIUS.to_str().is_ok());
+        assert!(ERR_USAGE_FACING.to_str().is_ok());
+        assert!(ERR_USAGE_LINE_OF_SIGHT.to_str().is_ok());
+        assert!(ERR_USAGE_PRIORITY.to_str().is_ok());
+        assert!(ERR_USAGE_BLACKLIST.to_str().is_ok());
+        assert!(ERR_USAGE_TRACE.to_str().is_ok());
+        assert!(ERR_USAGE_COMMAND.to_str().is_ok());
+        assert!(ERR_USAGE_HISTORY.to_str().is_ok());
+    }
+
+    // -------------------------------------------------------------------------
+    // InteractNearest tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_nearest_usage_error() {
+        let lua = lua();
+        assert_usage(&lua, "InteractNearest()", ERR_USAGE);
+        assert_usage(&lua, "InteractNearest('loot')", ERR_USAGE);
+        assert_usage(&lua, "InteractNearest({})", ERR_USAGE);
+        assert_eq!(AUTOLOOT.get(), None);
+    }
+
+    #[test]
+    fn test_nearest_passes_autoloot_and_returns_interact_result() {
+        let lua = lua();
+        lua.exec("assert(InteractNearest(1) == 1)").unwrap();
+        assert_eq!(AUTOLOOT.get(), Some(1));
+        // Returning 1 hands the argument back, as the client does
+        lua.exec("assert(InteractNearest('0') == '0')").unwrap();
+        assert_eq!(AUTOLOOT.get(), Some(0));
+        lua.exec("assert(select('#', InteractNearestFindsNothing(1)) == 0)")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_usage_error_can_be_caught_and_state_survives() {
+        let lua = lua();
+        lua.exec(
+            r#"
+            local ok, err = pcall(InteractNearest)
+            assert(not ok and err == "Usage: InteractNearest(autoloot)")
+            assert(InteractNearest(1) == 1)
+            "#,
+        )
+        .unwrap();
+    }
+
+    // -------------------------------------------------------------------------
+    // Setter tests
+    // -------------------------------------------------------------------------
+
+    fn range() -> RangeConfig {
+        *RANGE.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[test]
+    fn test_set_range_arguments() {
+        let _settings = lock_settings();
+        let lua = lua();
+        lua.exec("assert(select('#', InteractSetRange(6, 3, 0.5)) == 0)")
+            .unwrap();
+        assert_eq!(
+            range(),
+            RangeConfig {
+                max_distance: 6.0,
+                model: RangeModel::Cylindrical { max_height: 3.0 },
+                unit_radius: 0.5,
+                gameobject_radius: 0.0,
+            }
+        );
+
+        lua.exec("InteractSetRange(-1, nil, nil, 2)").unwrap();
+        assert_eq!(
+            range(),
+            RangeConfig {
+                max_distance: 0.0,
+                model: RangeModel::Spherical,
+                unit_radius: 0.0,
+                gameobject_radius: 2.0,
+            }
+        );
+
+        assert_usage(&lua, "InteractSetRange()", ERR_USAGE_RANGE);
+        assert_usage(&lua, "InteractSetRange(5, 'high')", ERR_USAGE_RANGE);
+        *RANGE.write().unwrap_or_else(PoisonError::into_inner) = RangeConfig::DEFAULT;
+    }
+
+    #[test]
+    fn test_get_range_round_trips() {
+        let _settings = lock_settings();
+        *RANGE.write().unwrap_or_else(PoisonError::into_inner) = RangeConfig::DEFAULT;
+        let lua = lua();
+        lua.exec(
+            r"
+            local distance, maxHeight, unitRadius, objectRadius = InteractGetRange()
+            assert(distance == 5 and maxHeight == 0)
+            assert(unitRadius == 0 and objectRadius == 0)
+
+            InteractSetRange(6, 3, 0.5, 1.5)
+            distance, maxHeight, unitRadius, objectRadius = InteractGetRange()
+            assert(distance == 6 and maxHeight == 3)
+            assert(unitRadius == 0.5 and objectRadius == 1.5)
+
+            -- Changing only the distance keeps the rest
+            local _, maxHeight, unitRadius, objectRadius = InteractGetRange()
+            InteractSetRange(8, maxHeight, unitRadius, objectRadius)
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            range(),
+            RangeConfig {
+                max_distance: 8.0,
+                model: RangeModel::Cylindrical { max_height: 3.0 },
+                unit_radius: 0.5,
+                gameobject_radius: 1.5,
+            }
+        );
+        *RANGE.write().unwrap_or_else(PoisonError::into_inner) = RangeConfig::DEFAULT;
+    }
+
+    #[test]
+    fn test_set_object_radius_arguments() {
+        let _settings = lock_settings();
+        let radius = |entry_id| {
+            OBJECT_RADII
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(entry_id, -1.0)
+        };
+        let lua = lua();
+        lua.exec("InteractSetObjectRadius(32349, 2)").unwrap();
+        assert_eq!(radius(32349), 2.0);
+        lua.exec("InteractSetObjectRadius(32349)").unwrap();
+        assert_eq!(radius(32349), -1.0);
+        lua.exec("InteractSetObjectRadius(32349, 2); InteractSetObjectRadius(nil)")
+            .unwrap();
+        assert!(OBJECT_RADII
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty());
+
+        assert_usage(
+            &lua,
+            "InteractSetObjectRadius('chest', 2)",
+            ERR_USAGE_OBJECT_RADIUS,
+        );
+        assert_usage(
+            &lua,
+            "InteractSetObjectRadius(1, 'big')",
+            ERR_USAGE_OBJECT_RADIUS,
+        );
+    }
+
+    #[test]
+    fn test_set_facing_arguments() {
+        let _settings = lock_settings();
+        let lua = lua();
+        lua.exec("InteractSetFacing(120, 2)").unwrap();
+        assert_eq!(
+            *FACING.read().unwrap_or_else(PoisonError::into_inner),
+            FacingConfig::from_degrees(120.0, 2.0)
+        );
+        assert_usage(&lua, "InteractSetFacing(120)", ERR_USAGE_FACING);
+        *FACING.write().unwrap_or_else(PoisonError::into_inner) = FacingConfig::default();
+    }
+
+    #[test]
+    fn test_set_line_of_sight_arguments() {
+        let _settings = lock_settings();
+        let los = || *LINE_OF_SIGHT.read().unwrap_or_else(PoisonError::into_inner);
+        let lua = lua();
+        lua.exec("InteractSetLineOfSight(1)").unwrap();
+        assert_eq!(
+            los(),
+            LineOfSightConfig {
+                enabled: true,
+                max_checks: 3,
+            }
+        );
+        lua.exec("InteractSetLineOfSight(0, 0)").unwrap();
+        assert_eq!(
+            los(),
+            LineOfSightConfig {
+                enabled: false,
+                max_checks: 1,
+            }
+        );
+        assert_usage(
+            &lua,
+            "InteractSetLineOfSight(1, 'x')",
+            ERR_USAGE_LINE_OF_SIGHT,
+        );
+        *LINE_OF_SIGHT
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = LineOfSightConfig::DEFAULT;
+    }
+
+    #[test]
+    fn test_set_name_filters_arguments() {
+        let _settings = lock_settings();
+        let lua = lua();
+        lua.exec("InteractSetNameFilters(nil, 'Dusty*')").unwrap();
+        {
+            let filter = crate::settings::name_filter();
+            assert!(filter.include().is_empty());
+            assert_eq!(filter.exclude(), ["dusty*"]);
+        }
+        assert_usage(&lua, "InteractSetNameFilters({})", ERR_USAGE_NAME_FILTERS);
+        *NAME_FILTER.write().unwrap_or_else(PoisonError::into_inner) = NameFilter::default();
+    }
+
+    #[test]
+    fn test_set_trace_arguments() {
+        let _settings = lock_settings();
+        let lua = lua();
+        lua.exec("assert(select('#', InteractSetTrace(1)) == 0)")
+            .unwrap();
+        assert!(crate::log::trace_enabled());
+        lua.exec("InteractSetTrace(0)").unwrap();
+        assert!(!crate::log::trace_enabled());
+        assert_usage(&lua, "InteractSetTrace('on')", ERR_USAGE_TRACE);
+    }
+
+    // -------------------------------------------------------------------------
+    // Result tests
+    // -------------------------------------------------------------------------
+
+    fn priority() -> Vec<Tier> {
+        PRIORITY
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    #[test]
+    fn test_push_result_returns_true_or_nil_and_message() {
+        let _settings = lock_settings();
+        let lua = lua();
+        lua.exec(
+            r#"
+            assert(select('#', InteractSetPriority("alive,lootable")) == 1)
+            assert(InteractSetPriority("alive,lootable") == true)
+            "#,
+        )
+        .unwrap();
+        assert_eq!(priority(), [Tier::Alive, Tier::Lootable]);
+
+        lua.exec(
+            r#"
+            assert(select('#', InteractSetPriority("lootable,mailbox")) == 2)
+            local ok, err = InteractSetPriority("lootable,mailbox")
+            assert(ok == nil and string.find(err, "mailbox"))
+            "#,
+        )
+        .unwrap();
+        assert_eq!(priority(), [Tier::Alive, Tier::Lootable]);
+
+        lua.exec("assert(InteractSetPriority(nil) == true)")
+            .unwrap();
+        assert_eq!(priority(), DEFAULT_PRIORITY);
+        assert_usage(&lua, "InteractSetPriority({})", ERR_USAGE_PRIORITY);
+    }
+
+    #[test]
+    fn test_set_blacklist_rejects_bad_ids_and_nil_restores_default() {
+        // Other tests rely on the default blacklist, so only invalid input
+        // and nil are tried here
+        let lua = lua();
+        lua.exec(
+            r#"
+            local ok, err = InteractSetBlacklist("1731,chest")
+            assert(ok == nil and string.find(err, "'chest' is not an entry ID"))
+            assert(InteractSetBlacklist(nil) == true)
+            "#,
+        )
+        .unwrap();
+        let mut default = config::DEFAULT_BLACKLIST.to_vec();
+        default.sort_unstable();
+        assert_eq!(game::blacklist(), default);
+        assert_usage(&lua, "InteractSetBlacklist({})", ERR_USAGE_BLACKLIST);
+    }
+
+    #[test]
+    fn test_push_path_returns_path_or_nil_and_message() {
+        lua()
+            .exec(
+                r#"
+                assert(InteractCaptureSnapshot() == "Logs\\interact_snapshot_1.json")
+                assert(select('#', InteractCaptureSnapshotNotInWorld()) == 2)
+                local path, err = InteractCaptureSnapshotNotInWorld()
+                assert(path == nil and err == "Not in world")
+                "#,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_push_history_table() {
+        let lua = lua();
+        lua.exec(
+            r#"
+            local history = InteractGetHistory()
+            assert(table.getn(history) == 2)
+            local last = history[1]
+            assert(last.time == 12.5 and last.guid == "0x0000000000000010")
+            assert(last.entry == 1731 and last.category == "gameobject")
+            assert(last.distance == 2.5 and last.outcome == "error")
+            assert(last.message == "You are too far away.")
+            local first = history[2]
+            assert(first.category == nil and first.outcome == "loot")
+            assert(first.message == nil)
+            assert(table.getn(InteractGetHistory(1)) == 1)
+            assert(table.getn(InteractGetHistory(0)) == 0)
+            assert(table.getn(InteractGetHistory(-3)) == 0)
+            "#,
+        )
+        .unwrap();
+        assert_usage(&lua, "InteractGetHistory('all')", ERR_USAGE_HISTORY);
+    }
+
+    #[test]
+    fn test_push_lines_joins_output() {
+        let lua = lua();
+        lua.exec(
+            r#"
+            local help = InteractCommand()
+            assert(string.find(help, "^/interact status"))
+            assert(string.find(help, "\n/interact reload"))
+            assert(string.find(InteractCommand("teleport"), "Unknown command"))
+            "#,
+        )
+        .unwrap();
+        assert_usage(&lua, "InteractCommand({})", ERR_USAGE_COMMAND);
+    }
+
+    #[test]
+    fn test_command_changes_settings() {
+        let _settings = lock_settings();
+        *RANGE.write().unwrap_or_else(PoisonError::into_inner) = RangeConfig::DEFAULT;
+        let lua = lua();
+        lua.exec(
+            r#"
+            assert(InteractCommand("range 6 3") == "Range: 6.0 yd (horizontal, height limit 3.0 yd)")
+            assert(InteractCommand("priority alive,lootable") == "Priority: alive, lootable")
+            "#,
+        )
+        .unwrap();
+        assert_eq!(range().model, RangeModel::Cylindrical { max_height: 3.0 });
+        assert_eq!(priority(), [Tier::Alive, Tier::Lootable]);
+        assert_eq!(CLIENT_COMMAND.take(), None);
+
+        lua.exec("InteractCommand('priority default')").unwrap();
+        assert_eq!(priority(), DEFAULT_PRIORITY);
+        *RANGE.write().unwrap_or_else(PoisonError::into_inner) = RangeConfig::DEFAULT;
+    }
+
+    #[test]
+    fn test_command_hands_client_commands_over() {
+        let lua = lua();
+        lua.exec("assert(InteractCommand('status') == 'from the client')")
+            .unwrap();
+        assert_eq!(CLIENT_COMMAND.take(), Some(Command::Status));
+        lua.exec("InteractCommand('reload')").unwrap();
+        assert_eq!(CLIENT_COMMAND.take(), Some(Command::Reload));
+    }
+
+    #[test]
+    fn test_push_version_capabilities() {
+        let lua = lua();
+        lua.exec(
+            r#"
+            assert(select('#', InteractGetVersion()) == 2)
+            local version, caps = InteractGetVersion()
+            assert(version == "1.2.1" and caps.version == version)
+            assert(caps.build == 5875 and caps.offsets == "1.12.1.5875")
+            assert(caps.functions.InteractNearest and caps.functions.InteractGetVersion)
+            assert(caps.functions.InteractTeleport == nil)
+            assert(caps.features.config and caps.features.lineOfSight == nil)
+
+            local _, unknown = InteractGetVersionUnknownBuild()
+            assert(unknown.build == nil and next(unknown.functions) == nil)
+            "#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_push_report_table() {
+        let lua = lua();
+        lua.exec(
+            r#"
+            local report = InteractDiagnostics()
+            assert(report.version == "1.2.1" and report.hooksInitialized == true)
+            assert(report.scriptHook == true and report.frameHook == false)
+            assert(report.eventHook == true)
+            assert(report.scheduledTasks == 3 and report.eventHandlers == 4)
+            assert(report.clientBuild == 5875 and report.buildMatches == true)
+            assert(report.inWorld == true and report.canInteract == true)
+            assert(report.objectManager == "0x12345678" and report.objectCount == 42)
+            assert(report.playerGuid == "0x0000000000000010")
+            assert(report.playerPointer == nil)
+            assert(report.lastError == "Failed to enable hook")
+            assert(report.configFound == false and report.configStatus == "ok")
+            assert(report.lines[1] == "Interact v1.2.1 diagnostics")
+            assert(report.lines[table.getn(report.lines)] == "Last error: Failed to enable hook")
+            "#,
+        )
+        .unwrap();
+    }
+}
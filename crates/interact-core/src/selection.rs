@@ -0,0 +1,1525 @@
+//! Candidate selection
+//!
+//! Range, facing and line of sight settings, the per-tier candidate
+//! tracking used by `InteractNearest`, and `select`, which walks a `World`
+//! and picks the object to interact with. The DLL's `World` reads the object
+//! manager and `interact-sim`'s reads a fixture; keeping the rules free of
+//! game memory lets them be tested on any platform.
+
+use crate::config::{Config, Tier, DEFAULT_PRIORITY};
+use crate::descriptors::{GameObjectDescriptor, GameObjectType, UnitDescriptor};
+use crate::game::{is_blacklisted, C3Vector, NameFilter, ObjectType, WorldState};
+use std::collections::HashMap;
+use std::fmt;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Maximum interaction distance in yards
+pub const MAX_DISTANCE: f32 = 5.0;
+
+/// Initial "infinite" distance for comparisons
+pub const INITIAL_DISTANCE: f32 = 1000.0;
+
+/// Objects closer than this are never rejected by the facing cone, since the
+/// angle to an object you are standing on is meaningless
+pub const FACING_MIN_DISTANCE: f32 = 1.0;
+
+// =============================================================================
+// Priority
+// =============================================================================
+
+/// Take the first valid candidate in priority order. `tiers` is indexed by
+/// `Tier`; tiers missing from `order` are never selected.
+pub fn select_by_priority<P: Copy + Default>(
+    mut tiers: [Candidate<P>; Tier::COUNT],
+    order: &[Tier],
+) -> Option<Candidate<P>> {
+    let tier = order
+        .iter()
+        .find(|tier| tiers[**tier as usize].is_valid())?;
+    let mut candidate = std::mem::take(&mut tiers[*tier as usize]);
+    candidate.tier = Some(*tier);
+    Some(candidate)
+}
+
+// =============================================================================
+// Line of Sight
+// =============================================================================
+
+/// Line of sight checking of the best candidates.
+///
+/// Tracing a ray through the world is far more expensive than reading object
+/// fields, so only the top `max_checks` candidates in priority order are
+/// traced. If none of them are visible, nothing is selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineOfSightConfig {
+    pub enabled: bool,
+    pub max_checks: u32,
+}
+
+impl LineOfSightConfig {
+    /// Disabled, checking up to 3 candidates once enabled
+    pub const DEFAULT: Self = Self {
+        enabled: false,
+        max_checks: 3,
+    };
+
+    /// Whether another candidate may be traced after `rejected` have failed
+    pub fn should_check(&self, rejected: usize) -> bool {
+        self.enabled && rejected < self.max_checks as usize
+    }
+}
+
+// =============================================================================
+// Range
+// =============================================================================
+
+/// How the distance between the player and an object is measured
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RangeModel {
+    /// Full 3D distance (original behaviour)
+    Spherical,
+    /// Horizontal distance, with a separate limit on the height difference.
+    /// Suits herbs on cliffs and mobs on ramps, where the 3D distance is
+    /// inflated by height the client does not care about.
+    Cylindrical { max_height: f32 },
+}
+
+/// Range check applied to every object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeConfig {
+    pub max_distance: f32,
+    pub model: RangeModel,
+    /// Bounding radius subtracted from the distance to units
+    pub unit_radius: f32,
+    /// Bounding radius subtracted from the distance to game objects
+    pub gameobject_radius: f32,
+}
+
+impl RangeConfig {
+    /// 3D distance within 5 yards of the object's origin
+    pub const DEFAULT: Self = Self {
+        max_distance: MAX_DISTANCE,
+        model: RangeModel::Spherical,
+        unit_radius: 0.0,
+        gameobject_radius: 0.0,
+    };
+
+    /// Bounding radius for an object type
+    pub fn radius(&self, obj_type: ObjectType) -> f32 {
+        match obj_type {
+            ObjectType::Unit => self.unit_radius,
+            ObjectType::GameObject => self.gameobject_radius,
+            _ => 0.0,
+        }
+    }
+
+    /// Distance from `from` to the edge of an object at `to` with the given
+    /// bounding `radius`, or `None` if it is out of range
+    pub fn measure(&self, from: &C3Vector, to: &C3Vector, radius: f32) -> Option<f32> {
+        let distance = match self.model {
+            RangeModel::Spherical => from.distance(to),
+            RangeModel::Cylindrical { max_height } => {
+                if (to.z - from.z).abs() > max_height {
+                    return None;
+                }
+                from.distance_2d(to)
+            }
+        };
+        let distance = (distance - radius).max(0.0);
+        (distance <= self.max_distance).then_some(distance)
+    }
+}
+
+/// Bounding radii of individual game objects, keyed by entry ID.
+///
+/// Mailboxes, large chests and many quest objects have their origin well
+/// away from where the player stands to use them. The 1.12 client does not
+/// keep model bounds anywhere we can read cheaply, so radii are configured
+/// per entry and fall back to `RangeConfig::gameobject_radius`.
+#[derive(Debug, Default)]
+pub struct ObjectRadii(HashMap<u32, f32>);
+
+impl ObjectRadii {
+    /// Set the radius for an entry; zero or less removes it
+    pub fn set(&mut self, entry_id: u32, radius: f32) {
+        if radius > 0.0 {
+            self.0.insert(entry_id, radius);
+        } else {
+            self.0.remove(&entry_id);
+        }
+    }
+
+    /// Radius for an entry, or `default` if none is configured
+    pub fn get(&self, entry_id: u32, default: f32) -> f32 {
+        self.0.get(&entry_id).copied().unwrap_or(default)
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// =============================================================================
+// Facing
+// =============================================================================
+
+/// Facing-aware scoring of candidates.
+///
+/// The default (full cone, no weight) reproduces pure distance selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacingConfig {
+    /// Half-angle of the accepted cone in radians; `π` accepts everything
+    pub cone: f32,
+    /// Yards added to the score of an object directly behind the player,
+    /// scaled linearly with the angle
+    pub weight: f32,
+}
+
+impl Default for FacingConfig {
+    fn default() -> Self {
+        Self {
+            cone: std::f32::consts::PI,
+            weight: 0.0,
+        }
+    }
+}
+
+impl FacingConfig {
+    /// Build from a full cone width in degrees (0 or >= 360 disables the
+    /// cone) and an angle weight in yards
+    pub fn from_degrees(cone_degrees: f32, weight: f32) -> Self {
+        let cone = if cone_degrees <= 0.0 || cone_degrees >= 360.0 {
+            std::f32::consts::PI
+        } else {
+            (cone_degrees / 2.0).to_radians()
+        };
+        Self {
+            cone,
+            weight: weight.max(0.0),
+        }
+    }
+
+    /// Whether the player's facing needs to be read at all
+    pub fn is_enabled(&self) -> bool {
+        self.cone < std::f32::consts::PI || self.weight > 0.0
+    }
+
+    /// Score a candidate at `distance` yards and `angle` radians off the
+    /// player's facing. Returns `None` if it falls outside the cone.
+    pub fn score(&self, distance: f32, angle: f32) -> Option<f32> {
+        if distance > FACING_MIN_DISTANCE && angle > self.cone {
+            return None;
+        }
+        Some(distance + self.weight * angle / std::f32::consts::PI)
+    }
+}
+
+// =============================================================================
+// Candidate tracking
+// =============================================================================
+
+/// Tracks the best candidate for a given priority level
+///
+/// Generic over the pointer payload so the selection logic can be tested
+/// without game memory; the DLL stores typed object pointers. Candidates are ranked
+/// by `score`, which equals `distance` unless facing weighting is enabled.
+#[derive(Default)]
+pub struct Candidate<P> {
+    pub guid: u64,
+    pub pointer: P,
+    pub obj_type: ObjectType,
+    pub distance: f32,
+    pub score: f32,
+    /// Tier the candidate was selected from, set by `select_by_priority`
+    pub tier: Option<Tier>,
+}
+
+impl<P: Copy + Default> Candidate<P> {
+    pub fn new() -> Self {
+        Self {
+            guid: 0,
+            pointer: P::default(),
+            obj_type: ObjectType::None,
+            distance: INITIAL_DISTANCE,
+            score: INITIAL_DISTANCE,
+            tier: None,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.obj_type != ObjectType::None
+    }
+
+    /// Update ranking by distance only
+    #[cfg(test)]
+    pub fn update(&mut self, guid: u64, pointer: P, obj_type: ObjectType, distance: f32) {
+        self.update_scored(guid, pointer, obj_type, distance, distance);
+    }
+
+    pub fn update_scored(
+        &mut self,
+        guid: u64,
+        pointer: P,
+        obj_type: ObjectType,
+        distance: f32,
+        score: f32,
+    ) {
+        if score < self.score {
+            self.guid = guid;
+            self.pointer = pointer;
+            self.obj_type = obj_type;
+            self.distance = distance;
+            self.score = score;
+        }
+    }
+}
+
+// =============================================================================
+// Settings
+// =============================================================================
+
+/// Every setting that decides which object is selected, except the
+/// blacklist, which is global (`game::set_blacklist`)
+#[derive(Debug)]
+pub struct Settings {
+    pub range: RangeConfig,
+    pub radii: ObjectRadii,
+    pub facing: FacingConfig,
+    pub line_of_sight: LineOfSightConfig,
+    pub filter: NameFilter,
+    pub priority: Vec<Tier>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            range: RangeConfig::DEFAULT,
+            radii: ObjectRadii::default(),
+            facing: FacingConfig::default(),
+            line_of_sight: LineOfSightConfig::DEFAULT,
+            filter: NameFilter::default(),
+            priority: DEFAULT_PRIORITY.to_vec(),
+        }
+    }
+}
+
+impl From<&Config> for Settings {
+    fn from(config: &Config) -> Self {
+        let range = &config.range;
+        let mut radii = ObjectRadii::default();
+        for (entry_id, radius) in config.object_radii() {
+            radii.set(entry_id, radius);
+        }
+        Self {
+            range: RangeConfig {
+                max_distance: range.distance,
+                model: if range.max_height > 0.0 {
+                    RangeModel::Cylindrical {
+                        max_height: range.max_height,
+                    }
+                } else {
+                    RangeModel::Spherical
+                },
+                unit_radius: range.unit_radius,
+                gameobject_radius: range.object_radius,
+            },
+            radii,
+            facing: FacingConfig::from_degrees(config.facing.cone, config.facing.weight),
+            line_of_sight: LineOfSightConfig {
+                enabled: config.line_of_sight.enabled,
+                max_checks: config.line_of_sight.max_checks,
+            },
+            filter: NameFilter::parse(&config.filters.include, &config.filters.exclude),
+            priority: config.priority.clone(),
+        }
+    }
+}
+
+impl Settings {
+    /// Borrow the settings for `select`
+    pub fn rules(&self) -> Rules<'_> {
+        Rules {
+            range: self.range,
+            radii: &self.radii,
+            facing: self.facing,
+            line_of_sight: self.line_of_sight,
+            filter: &self.filter,
+            priority: &self.priority,
+        }
+    }
+}
+
+/// Borrowed view of `Settings`. The DLL keeps each setting behind its own
+/// lock and builds this from the read guards.
+#[derive(Debug, Clone, Copy)]
+pub struct Rules<'a> {
+    pub range: RangeConfig,
+    pub radii: &'a ObjectRadii,
+    pub facing: FacingConfig,
+    pub line_of_sight: LineOfSightConfig,
+    pub filter: &'a NameFilter,
+    pub priority: &'a [Tier],
+}
+
+// =============================================================================
+// Selection
+// =============================================================================
+
+/// The objects `select` chooses from. The DLL implements this over the
+/// client's object manager, `interact-sim` over a world fixture.
+///
+/// Fields are only read once the cheaper checks have passed, in the order
+/// `select` needs them, so implementations should read lazily.
+pub trait World {
+    /// Handle to an object, kept in the selected candidate
+    type Object: Copy;
+
+    /// Every visible object and its GUID, the player included
+    fn objects(&self) -> impl Iterator<Item = (u64, Self::Object)>;
+
+    /// Type of an object; only units and game objects can be selected
+    fn object_type(&self, object: Self::Object) -> ObjectType;
+
+    /// Position of a unit or game object
+    fn position(&self, object: Self::Object) -> C3Vector;
+
+    /// Name of an object, if it has one
+    fn name(&self, object: Self::Object) -> Option<String>;
+
+    /// GUID of whoever summoned a unit, or 0
+    fn summoned_by(&self, object: Self::Object) -> u64;
+
+    /// Type of the visible object with `guid`, or `None` if there is none
+    fn type_of_guid(&self, guid: u64) -> Option<ObjectType>;
+
+    /// Descriptor fields of a unit
+    fn unit_descriptor(&self, object: Self::Object) -> UnitDescriptor;
+
+    /// Entry ID of a unit or game object; the selection only reads game
+    /// object entries
+    fn entry_id(&self, object: Self::Object) -> u32;
+
+    /// Descriptor fields of a game object
+    fn gameobject_descriptor(&self, object: Self::Object) -> GameObjectDescriptor;
+
+    /// Whether nothing blocks the view from `from` to an object
+    fn in_line_of_sight(&self, from: &C3Vector, object: Self::Object) -> bool;
+}
+
+/// The player `select` chooses for
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerView {
+    pub guid: u64,
+    pub position: C3Vector,
+    /// Facing in radians; only used when `FacingConfig::is_enabled`
+    pub facing: f32,
+}
+
+/// Why an object was or was not a candidate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    /// Not a unit or game object
+    WrongType(ObjectType),
+    /// A unit summoned by a player (pets, totems, guardians)
+    SummonedByPlayer,
+    OutOfRange,
+    /// In range but outside the facing cone
+    OutsideFacingCone {
+        distance: f32,
+    },
+    /// Rejected by the name filters
+    FilteredByName,
+    Blacklisted,
+    /// Door, spell focus, or locked, in use or not interactable
+    NotInteractable,
+    /// Someone else's fishing bobber
+    OtherFishingBobber,
+    /// Dead, with nothing to loot or skin
+    DeadEmpty,
+    /// Alive but with no health or not selectable
+    Unselectable,
+    /// Qualifies for a tier; the lowest score of each tier competes
+    Candidate {
+        tier: Tier,
+        distance: f32,
+        score: f32,
+    },
+    /// Was selected, but failed the line of sight check
+    NoLineOfSight {
+        distance: f32,
+    },
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongType(obj_type) => write!(f, "not selectable ({obj_type:?})"),
+            Self::SummonedByPlayer => write!(f, "summoned by a player"),
+            Self::OutOfRange => write!(f, "out of range"),
+            Self::OutsideFacingCone { distance } => {
+                write!(f, "outside facing cone ({distance:.2} yd)")
+            }
+            Self::FilteredByName => write!(f, "filtered by name"),
+            Self::Blacklisted => write!(f, "blacklisted"),
+            Self::NotInteractable => write!(f, "not interactable"),
+            Self::OtherFishingBobber => write!(f, "another player's fishing bobber"),
+            Self::DeadEmpty => write!(f, "dead, not lootable or skinnable"),
+            Self::Unselectable => write!(f, "not selectable"),
+            Self::Candidate {
+                tier,
+                distance,
+                score,
+            } => write!(
+                f,
+                "{} candidate ({distance:.2} yd, score {score:.2})",
+                tier.name()
+            ),
+            Self::NoLineOfSight { distance } => write!(f, "no line of sight ({distance:.2} yd)"),
+        }
+    }
+}
+
+/// Choose the object `InteractNearest` interacts with.
+///
+/// The visible objects are walked once, and every object is passed to
+/// `explain` with its verdict. With line of sight checks enabled, the best
+/// `max_checks` candidates of each tier are kept and traced in priority
+/// order; each one that fails is explained again as `NoLineOfSight`.
+pub fn select<W: World>(
+    world: &W,
+    player: &PlayerView,
+    state: WorldState,
+    rules: &Rules,
+    mut explain: impl FnMut(u64, W::Object, Verdict),
+) -> Option<Candidate<Option<W::Object>>> {
+    // At most max_checks candidates are traced, and at least one
+    let keep = if rules.line_of_sight.enabled {
+        (rules.line_of_sight.max_checks as usize).max(1)
+    } else {
+        1
+    };
+    let mut tiers: [Vec<Candidate<Option<W::Object>>>; Tier::COUNT] =
+        std::array::from_fn(|_| Vec::new());
+
+    for (guid, object) in world.objects() {
+        let verdict = match classify(world, player, rules, object) {
+            Ok((tier, distance, score)) => {
+                let mut candidate = Candidate::new();
+                candidate.update_scored(
+                    guid,
+                    Some(object),
+                    world.object_type(object),
+                    distance,
+                    score,
+                );
+                shortlist(&mut tiers[tier as usize], candidate, keep);
+                Verdict::Candidate {
+                    tier,
+                    distance,
+                    score,
+                }
+            }
+            Err(verdict) => verdict,
+        };
+        explain(guid, object, verdict);
+    }
+
+    // Ghosts can only interact with living NPCs (spirit healer)
+    if !state.can_loot() {
+        for (i, candidates) in tiers.iter_mut().enumerate() {
+            if i != Tier::Alive as usize {
+                candidates.clear();
+            }
+        }
+    }
+
+    let mut rejected = 0;
+    for tier in rules.priority {
+        for mut candidate in std::mem::take(&mut tiers[*tier as usize]) {
+            let object = candidate.pointer?;
+            if !rules.line_of_sight.enabled || world.in_line_of_sight(&player.position, object) {
+                candidate.tier = Some(*tier);
+                return Some(candidate);
+            }
+
+            explain(
+                candidate.guid,
+                object,
+                Verdict::NoLineOfSight {
+                    distance: candidate.distance,
+                },
+            );
+            rejected += 1;
+            if !rules.line_of_sight.should_check(rejected) {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+/// Insert a valid candidate into a tier's list, kept sorted by score and at
+/// most `keep` long. Of equal scores the first one walked ranks first, as
+/// with `Candidate::update_scored`.
+fn shortlist<P: Copy + Default>(
+    candidates: &mut Vec<Candidate<P>>,
+    candidate: Candidate<P>,
+    keep: usize,
+) {
+    if !candidate.is_valid() {
+        return;
+    }
+    let at = candidates.partition_point(|other| other.score <= candidate.score);
+    if at < keep {
+        candidates.insert(at, candidate);
+        candidates.truncate(keep);
+    }
+}
+
+/// Tier, distance and score of an object, or why it is not a candidate
+fn classify<W: World>(
+    world: &W,
+    player: &PlayerView,
+    rules: &Rules,
+    object: W::Object,
+) -> Result<(Tier, f32, f32), Verdict> {
+    let (obj_pos, radius) = candidate_bounds(world, &rules.range, rules.radii, object)?;
+
+    // Measure with the configured range model, skipping objects out of range
+    let distance = rules
+        .range
+        .measure(&player.position, &obj_pos, radius)
+        .ok_or(Verdict::OutOfRange)?;
+
+    let score = if rules.facing.is_enabled() {
+        let angle = player.position.facing_offset(player.facing, &obj_pos);
+        rules
+            .facing
+            .score(distance, angle)
+            .ok_or(Verdict::OutsideFacingCone { distance })?
+    } else {
+        distance
+    };
+
+    // Names are only read when a filter is active
+    if !rules.filter.is_empty() && !rules.filter.allows(world.name(object).as_deref()) {
+        return Err(Verdict::FilteredByName);
+    }
+
+    let tier = if world.object_type(object) == ObjectType::Unit {
+        unit_tier(&world.unit_descriptor(object))?
+    } else {
+        gameobject_tier(world, player, object)?
+    };
+    Ok((tier, distance, score))
+}
+
+/// Position and bounding radius of an object that can be a candidate,
+/// skipping units summoned by players
+pub fn candidate_bounds<W: World>(
+    world: &W,
+    range: &RangeConfig,
+    radii: &ObjectRadii,
+    object: W::Object,
+) -> Result<(C3Vector, f32), Verdict> {
+    let radius = match world.object_type(object) {
+        ObjectType::Unit => {
+            let summoned_by = world.summoned_by(object);
+            if summoned_by != 0 && world.type_of_guid(summoned_by) == Some(ObjectType::Player) {
+                return Err(Verdict::SummonedByPlayer);
+            }
+            range.radius(ObjectType::Unit)
+        }
+        ObjectType::GameObject => {
+            let default = range.radius(ObjectType::GameObject);
+            radii.get(world.entry_id(object), default)
+        }
+        obj_type => return Err(Verdict::WrongType(obj_type)),
+    };
+    Ok((world.position(object), radius))
+}
+
+/// Tier of a unit from its descriptor
+fn unit_tier(descriptor: &UnitDescriptor) -> Result<Tier, Verdict> {
+    if descriptor.is_dead() {
+        if descriptor.is_lootable() {
+            Ok(Tier::Lootable)
+        } else if descriptor.is_skinnable() {
+            Ok(Tier::Skinnable)
+        } else {
+            Err(Verdict::DeadEmpty)
+        }
+    } else if descriptor.health > 0 && descriptor.is_selectable() {
+        // Unselectable units such as invisible triggers are skipped
+        Ok(Tier::Alive)
+    } else {
+        Err(Verdict::Unselectable)
+    }
+}
+
+/// Tier of a game object, checking the blacklist and descriptor
+fn gameobject_tier<W: World>(
+    world: &W,
+    player: &PlayerView,
+    object: W::Object,
+) -> Result<Tier, Verdict> {
+    if is_blacklisted(world.entry_id(object)) {
+        return Err(Verdict::Blacklisted);
+    }
+
+    // Skip doors, spell foci and locked or in-use objects
+    let descriptor = world.gameobject_descriptor(object);
+    if !descriptor.is_interactable() {
+        return Err(Verdict::NotInteractable);
+    }
+
+    // Only our own fishing bobber can be clicked
+    if descriptor.go_type == GameObjectType::FishingNode && !descriptor.is_created_by(player.guid) {
+        return Err(Verdict::OtherFishingBobber);
+    }
+
+    if descriptor.go_type.is_service() {
+        Ok(Tier::Service)
+    } else {
+        Ok(Tier::GameObject)
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)] // Exact float comparisons are intentional in these tests
+
+    use super::*;
+    use crate::config::{self, DEFAULT_PRIORITY};
+    use crate::descriptors::{DynamicFlags, GameObjectFlags, UnitFlags};
+
+    /// Candidates in tests carry plain integers instead of game pointers
+    type TestCandidate = Candidate<u32>;
+
+    // -------------------------------------------------------------------------
+    // Candidate tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_candidate_new_is_invalid() {
+        let c = TestCandidate::new();
+        assert!(!c.is_valid());
+        assert_eq!(c.obj_type, ObjectType::None);
+        assert_eq!(c.distance, INITIAL_DISTANCE);
+    }
+
+    #[test]
+    fn test_candidate_update_makes_valid() {
+        let mut c = TestCandidate::new();
+        c.update(123, 456, ObjectType::Unit, 3.0);
+
+        assert!(c.is_valid());
+        assert_eq!(c.guid, 123);
+        assert_eq!(c.pointer, 456);
+        assert_eq!(c.obj_type, ObjectType::Unit);
+        assert_eq!(c.distance, 3.0);
+    }
+
+    #[test]
+    fn test_candidate_update_closer_replaces() {
+        let mut c = TestCandidate::new();
+        c.update(100, 200, ObjectType::Unit, 5.0);
+        c.update(101, 201, ObjectType::Unit, 3.0);
+
+        // Should have the closer one
+        assert_eq!(c.guid, 101);
+        assert_eq!(c.pointer, 201);
+        assert_eq!(c.distance, 3.0);
+    }
+
+    #[test]
+    fn test_candidate_update_farther_ignored() {
+        let mut c = TestCandidate::new();
+        c.update(100, 200, ObjectType::Unit, 3.0);
+        c.update(101, 201, ObjectType::Unit, 5.0);
+
+        // Should still have the closer one
+        assert_eq!(c.guid, 100);
+        assert_eq!(c.pointer, 200);
+        assert_eq!(c.distance, 3.0);
+    }
+
+    #[test]
+    fn test_candidate_update_same_distance_ignored() {
+        let mut c = TestCandidate::new();
+        c.update(100, 200, ObjectType::Unit, 3.0);
+        c.update(101, 201, ObjectType::Unit, 3.0);
+
+        // First one should win (not strictly less than)
+        assert_eq!(c.guid, 100);
+        assert_eq!(c.pointer, 200);
+    }
+
+    #[test]
+    fn test_candidate_is_valid_check() {
+        let mut c = TestCandidate::new();
+        assert!(!c.is_valid());
+
+        c.obj_type = ObjectType::Unit;
+        assert!(c.is_valid());
+
+        c.obj_type = ObjectType::GameObject;
+        assert!(c.is_valid());
+
+        c.obj_type = ObjectType::None;
+        assert!(!c.is_valid());
+    }
+
+    // -------------------------------------------------------------------------
+    // Constants tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_max_distance_is_5_yards() {
+        assert_eq!(MAX_DISTANCE, 5.0);
+    }
+
+    #[test]
+    fn test_initial_distance_is_large() {
+        // Use const block for compile-time assertion
+        const _: () = assert!(INITIAL_DISTANCE > MAX_DISTANCE);
+        assert_eq!(INITIAL_DISTANCE, 1000.0);
+    }
+
+    // -------------------------------------------------------------------------
+    // Line of sight tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_line_of_sight_disabled_by_default() {
+        let los = LineOfSightConfig::DEFAULT;
+        assert!(!los.enabled);
+        assert!(!los.should_check(0));
+    }
+
+    #[test]
+    fn test_line_of_sight_checks_capped() {
+        let los = LineOfSightConfig {
+            enabled: true,
+            max_checks: 3,
+        };
+        assert!(los.should_check(0));
+        assert!(los.should_check(2));
+        assert!(!los.should_check(3));
+    }
+
+    // -------------------------------------------------------------------------
+    // Priority tests
+    // -------------------------------------------------------------------------
+
+    fn tiers_at(distances: [f32; Tier::COUNT]) -> [TestCandidate; Tier::COUNT] {
+        let mut guid = 0;
+        distances.map(|distance| {
+            guid += 1;
+            let mut candidate = TestCandidate::new();
+            if distance < INITIAL_DISTANCE {
+                candidate.update(guid, 1, ObjectType::Unit, distance);
+            }
+            candidate
+        })
+    }
+
+    #[test]
+    fn test_priority_default_prefers_lootable_over_nearer_tiers() {
+        let tiers = tiers_at([4.0, 1.0, 1.0, 1.0, 1.0]);
+        let selected = select_by_priority(tiers, &DEFAULT_PRIORITY).unwrap();
+        assert_eq!(selected.guid, 1);
+    }
+
+    #[test]
+    fn test_priority_falls_through_empty_tiers() {
+        let tiers = tiers_at([INITIAL_DISTANCE, INITIAL_DISTANCE, 3.0, 1.0, 1.0]);
+        let selected = select_by_priority(tiers, &DEFAULT_PRIORITY).unwrap();
+        assert_eq!(selected.guid, 3);
+        assert_eq!(selected.tier, Some(Tier::Skinnable));
+    }
+
+    #[test]
+    fn test_priority_custom_order() {
+        let tiers = tiers_at([1.0, 1.0, 1.0, 1.0, 1.0]);
+        let selected = select_by_priority(tiers, &[Tier::Service, Tier::Lootable]).unwrap();
+        assert_eq!(selected.guid, 5);
+        assert_eq!(selected.tier, Some(Tier::Service));
+    }
+
+    #[test]
+    fn test_priority_omitted_tiers_never_selected() {
+        let tiers = tiers_at([
+            INITIAL_DISTANCE,
+            INITIAL_DISTANCE,
+            INITIAL_DISTANCE,
+            1.0,
+            1.0,
+        ]);
+        assert!(select_by_priority(tiers, &[Tier::Lootable, Tier::GameObject]).is_none());
+    }
+
+    // -------------------------------------------------------------------------
+    // Range tests
+    // -------------------------------------------------------------------------
+
+    fn pos(x: f32, y: f32, z: f32) -> C3Vector {
+        C3Vector { y, x, z }
+    }
+
+    #[test]
+    fn test_range_default_matches_3d_distance() {
+        let range = RangeConfig::DEFAULT;
+        assert_eq!(range.max_distance, MAX_DISTANCE);
+        assert_eq!(range.model, RangeModel::Spherical);
+        assert_eq!(
+            range.measure(&pos(0.0, 0.0, 0.0), &pos(3.0, 4.0, 0.0), 0.0),
+            Some(5.0)
+        );
+        assert_eq!(
+            range.measure(&pos(0.0, 0.0, 0.0), &pos(3.0, 4.0, 1.0), 0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_range_cylindrical_ignores_height_within_limit() {
+        // Herb 4 yd away horizontally but 4 yd up a cliff: 3D distance ~5.66
+        let range = RangeConfig {
+            model: RangeModel::Cylindrical { max_height: 5.0 },
+            ..RangeConfig::DEFAULT
+        };
+        let herb = pos(4.0, 0.0, 4.0);
+        assert_eq!(
+            RangeConfig::DEFAULT.measure(&pos(0.0, 0.0, 0.0), &herb, 0.0),
+            None
+        );
+        assert_eq!(range.measure(&pos(0.0, 0.0, 0.0), &herb, 0.0), Some(4.0));
+    }
+
+    #[test]
+    fn test_range_cylindrical_rejects_height_over_limit() {
+        // Corpse on the floor below: close horizontally, far vertically
+        let range = RangeConfig {
+            model: RangeModel::Cylindrical { max_height: 3.0 },
+            ..RangeConfig::DEFAULT
+        };
+        assert_eq!(
+            range.measure(&pos(0.0, 0.0, 0.0), &pos(1.0, 0.0, -3.5), 0.0),
+            None
+        );
+        assert_eq!(
+            range.measure(&pos(0.0, 0.0, 0.0), &pos(1.0, 0.0, -2.5), 0.0),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_range_radius_extends_reach() {
+        let range = RangeConfig {
+            gameobject_radius: 1.5,
+            ..RangeConfig::DEFAULT
+        };
+        let radius = range.radius(ObjectType::GameObject);
+        assert_eq!(radius, 1.5);
+        assert_eq!(range.radius(ObjectType::Unit), 0.0);
+        assert_eq!(
+            range.measure(&pos(0.0, 0.0, 0.0), &pos(6.0, 0.0, 0.0), radius),
+            Some(4.5)
+        );
+    }
+
+    #[test]
+    fn test_range_radius_never_negative() {
+        let range = RangeConfig {
+            unit_radius: 3.0,
+            ..RangeConfig::DEFAULT
+        };
+        assert_eq!(
+            range.measure(&pos(0.0, 0.0, 0.0), &pos(1.0, 0.0, 0.0), 3.0),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_object_radii_fall_back_to_default() {
+        let mut radii = ObjectRadii::default();
+        assert_eq!(radii.get(32349, 0.5), 0.5);
+
+        radii.set(32349, 2.0);
+        assert_eq!(radii.get(32349, 0.5), 2.0);
+        assert_eq!(radii.get(1, 0.5), 0.5);
+        assert_eq!(radii.len(), 1);
+    }
+
+    #[test]
+    fn test_object_radii_zero_removes_entry() {
+        let mut radii = ObjectRadii::default();
+        radii.set(32349, 2.0);
+        radii.set(32349, 0.0);
+        assert_eq!(radii.len(), 0);
+
+        radii.set(1, -1.0);
+        assert_eq!(radii.len(), 0);
+    }
+
+    #[test]
+    fn test_object_radius_brings_large_object_in_range() {
+        // Large object with its origin 6 yards away and its edge within reach
+        let range = RangeConfig::DEFAULT;
+        let mut radii = ObjectRadii::default();
+        let (from, to) = (pos(0.0, 0.0, 0.0), pos(6.0, 0.0, 0.0));
+        assert_eq!(range.measure(&from, &to, radii.get(32349, 0.0)), None);
+
+        radii.set(32349, 2.0);
+        assert_eq!(range.measure(&from, &to, radii.get(32349, 0.0)), Some(4.0));
+    }
+
+    // -------------------------------------------------------------------------
+    // Facing tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_facing_default_is_pure_distance() {
+        let facing = FacingConfig::default();
+        assert!(!facing.is_enabled());
+        assert_eq!(facing.score(3.0, std::f32::consts::PI), Some(3.0));
+    }
+
+    #[test]
+    fn test_facing_from_degrees_disables_full_cone() {
+        assert!(!FacingConfig::from_degrees(0.0, 0.0).is_enabled());
+        assert!(!FacingConfig::from_degrees(360.0, 0.0).is_enabled());
+        assert!(FacingConfig::from_degrees(90.0, 0.0).is_enabled());
+        assert!(FacingConfig::from_degrees(0.0, 1.0).is_enabled());
+    }
+
+    #[test]
+    fn test_facing_negative_weight_clamped() {
+        let facing = FacingConfig::from_degrees(0.0, -3.0);
+        assert_eq!(facing.weight, 0.0);
+    }
+
+    #[test]
+    fn test_facing_cone_rejects_outside() {
+        // 90 degree cone = 45 degrees either side
+        let facing = FacingConfig::from_degrees(90.0, 0.0);
+        assert_eq!(facing.score(3.0, 30_f32.to_radians()), Some(3.0));
+        assert_eq!(facing.score(3.0, 60_f32.to_radians()), None);
+    }
+
+    #[test]
+    fn test_facing_cone_ignores_very_close_objects() {
+        let facing = FacingConfig::from_degrees(90.0, 0.0);
+        assert!(facing
+            .score(FACING_MIN_DISTANCE, std::f32::consts::PI)
+            .is_some());
+    }
+
+    #[test]
+    fn test_facing_weight_penalizes_behind() {
+        let facing = FacingConfig::from_degrees(0.0, 2.0);
+        let ahead = facing.score(3.0, 0.0).unwrap();
+        let behind = facing.score(3.0, std::f32::consts::PI).unwrap();
+        assert_eq!(ahead, 3.0);
+        assert!((behind - 5.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_facing_weight_prefers_object_in_front() {
+        // A corpse 2.5 yd ahead beats one 2 yd directly behind with weight 2
+        let facing = FacingConfig::from_degrees(0.0, 2.0);
+        let mut c = TestCandidate::new();
+        c.update_scored(
+            1,
+            10,
+            ObjectType::Unit,
+            2.0,
+            facing.score(2.0, std::f32::consts::PI).unwrap(),
+        );
+        c.update_scored(
+            2,
+            20,
+            ObjectType::Unit,
+            2.5,
+            facing.score(2.5, 0.0).unwrap(),
+        );
+        assert_eq!(c.guid, 2);
+        assert_eq!(c.distance, 2.5);
+    }
+
+    // -------------------------------------------------------------------------
+    // Priority selection tests (simulated)
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_priority_lootable_wins_over_all() {
+        let mut lootable = TestCandidate::new();
+        let mut gameobject = TestCandidate::new();
+        let mut skinnable = TestCandidate::new();
+        let mut alive = TestCandidate::new();
+
+        lootable.update(1, 100, ObjectType::Unit, 4.0);
+        gameobject.update(2, 200, ObjectType::GameObject, 2.0);
+        skinnable.update(3, 300, ObjectType::Unit, 1.0);
+        alive.update(4, 400, ObjectType::Unit, 0.5);
+
+        // Simulate priority selection
+        let winner = if lootable.is_valid() {
+            &lootable
+        } else if gameobject.is_valid() {
+            &gameobject
+        } else if skinnable.is_valid() {
+            &skinnable
+        } else {
+            &alive
+        };
+
+        assert_eq!(winner.guid, 1); // Lootable wins even though farther
+    }
+
+    #[test]
+    fn test_priority_gameobject_wins_over_skinnable_and_alive() {
+        let lootable = TestCandidate::new();
+        let mut gameobject = TestCandidate::new();
+        let mut skinnable = TestCandidate::new();
+        let mut alive = TestCandidate::new();
+
+        // No lootable
+        gameobject.update(2, 200, ObjectType::GameObject, 4.0);
+        skinnable.update(3, 300, ObjectType::Unit, 2.0);
+        alive.update(4, 400, ObjectType::Unit, 1.0);
+
+        let winner = if lootable.is_valid() {
+            &lootable
+        } else if gameobject.is_valid() {
+            &gameobject
+        } else if skinnable.is_valid() {
+            &skinnable
+        } else {
+            &alive
+        };
+
+        assert_eq!(winner.guid, 2); // GameObject wins
+    }
+
+    #[test]
+    fn test_priority_skinnable_wins_over_alive() {
+        let lootable = TestCandidate::new();
+        let gameobject = TestCandidate::new();
+        let mut skinnable = TestCandidate::new();
+        let mut alive = TestCandidate::new();
+
+        // No lootable or gameobject
+        skinnable.update(3, 300, ObjectType::Unit, 4.0);
+        alive.update(4, 400, ObjectType::Unit, 1.0);
+
+        let winner = if lootable.is_valid() {
+            &lootable
+        } else if gameobject.is_valid() {
+            &gameobject
+        } else if skinnable.is_valid() {
+            &skinnable
+        } else {
+            &alive
+        };
+
+        assert_eq!(winner.guid, 3); // Skinnable wins
+    }
+
+    #[test]
+    fn test_priority_alive_is_last_resort() {
+        let lootable = TestCandidate::new();
+        let gameobject = TestCandidate::new();
+        let skinnable = TestCandidate::new();
+        let mut alive = TestCandidate::new();
+
+        // Only alive unit
+        alive.update(4, 400, ObjectType::Unit, 1.0);
+
+        let winner = if lootable.is_valid() {
+            &lootable
+        } else if gameobject.is_valid() {
+            &gameobject
+        } else if skinnable.is_valid() {
+            &skinnable
+        } else {
+            &alive
+        };
+
+        assert_eq!(winner.guid, 4);
+    }
+
+    #[test]
+    fn test_no_candidates_returns_none() {
+        let lootable = TestCandidate::new();
+        let gameobject = TestCandidate::new();
+        let skinnable = TestCandidate::new();
+        let alive = TestCandidate::new();
+
+        let has_winner = lootable.is_valid()
+            || gameobject.is_valid()
+            || skinnable.is_valid()
+            || alive.is_valid();
+
+        assert!(!has_winner);
+    }
+
+    // -------------------------------------------------------------------------
+    // Select tests
+    // -------------------------------------------------------------------------
+
+    /// Object in a `TestWorld`
+    #[derive(Clone, Copy, Default)]
+    struct TestObject {
+        guid: u64,
+        obj_type: ObjectType,
+        position: C3Vector,
+        unit: UnitDescriptor,
+        entry_id: u32,
+        gameobject: GameObjectDescriptor,
+        visible: bool,
+    }
+
+    const PLAYER_GUID: u64 = 1;
+
+    /// Objects referred to by their index
+    struct TestWorld(Vec<TestObject>);
+
+    impl World for TestWorld {
+        type Object = usize;
+
+        fn objects(&self) -> impl Iterator<Item = (u64, usize)> {
+            self.0
+                .iter()
+                .enumerate()
+                .map(|(i, object)| (object.guid, i))
+        }
+
+        fn object_type(&self, object: usize) -> ObjectType {
+            self.0[object].obj_type
+        }
+
+        fn position(&self, object: usize) -> C3Vector {
+            self.0[object].position
+        }
+
+        fn name(&self, object: usize) -> Option<String> {
+            Some(format!("Object {}", self.0[object].guid))
+        }
+
+        fn summoned_by(&self, object: usize) -> u64 {
+            self.0[object].unit.summoned_by
+        }
+
+        fn type_of_guid(&self, guid: u64) -> Option<ObjectType> {
+            self.0.iter().find(|o| o.guid == guid).map(|o| o.obj_type)
+        }
+
+        fn unit_descriptor(&self, object: usize) -> UnitDescriptor {
+            self.0[object].unit
+        }
+
+        fn entry_id(&self, object: usize) -> u32 {
+            self.0[object].entry_id
+        }
+
+        fn gameobject_descriptor(&self, object: usize) -> GameObjectDescriptor {
+            self.0[object].gameobject
+        }
+
+        fn in_line_of_sight(&self, _from: &C3Vector, object: usize) -> bool {
+            self.0[object].visible
+        }
+    }
+
+    fn at(x: f32) -> C3Vector {
+        C3Vector { x, y: 0.0, z: 0.0 }
+    }
+
+    fn player() -> TestObject {
+        TestObject {
+            guid: PLAYER_GUID,
+            obj_type: ObjectType::Player,
+            visible: true,
+            ..TestObject::default()
+        }
+    }
+
+    fn alive(guid: u64, x: f32) -> TestObject {
+        TestObject {
+            guid,
+            obj_type: ObjectType::Unit,
+            position: at(x),
+            unit: UnitDescriptor {
+                health: 100,
+                ..UnitDescriptor::default()
+            },
+            visible: true,
+            ..TestObject::default()
+        }
+    }
+
+    fn corpse(guid: u64, x: f32, dynamic_flags: DynamicFlags, flags: UnitFlags) -> TestObject {
+        TestObject {
+            unit: UnitDescriptor {
+                health: 0,
+                dynamic_flags,
+                flags,
+                ..UnitDescriptor::default()
+            },
+            ..alive(guid, x)
+        }
+    }
+
+    fn object(guid: u64, x: f32, entry_id: u32, go_type: GameObjectType) -> TestObject {
+        TestObject {
+            guid,
+            obj_type: ObjectType::GameObject,
+            position: at(x),
+            entry_id,
+            gameobject: GameObjectDescriptor {
+                go_type,
+                ..GameObjectDescriptor::default()
+            },
+            visible: true,
+            ..TestObject::default()
+        }
+    }
+
+    fn view() -> PlayerView {
+        PlayerView {
+            guid: PLAYER_GUID,
+            position: at(0.0),
+            facing: 0.0,
+        }
+    }
+
+    /// Selected GUID and every verdict, in walk order
+    fn run(
+        world: &TestWorld,
+        state: WorldState,
+        settings: &Settings,
+    ) -> (Option<u64>, Vec<(u64, Verdict)>) {
+        let mut verdicts = Vec::new();
+        let candidate = select(world, &view(), state, &settings.rules(), |guid, _, v| {
+            verdicts.push((guid, v));
+        });
+        (candidate.map(|c| c.guid), verdicts)
+    }
+
+    fn in_world() -> WorldState {
+        WorldState {
+            in_world: true,
+            ..WorldState::default()
+        }
+    }
+
+    #[test]
+    fn test_select_prefers_tier_over_distance() {
+        let world = TestWorld(vec![
+            player(),
+            alive(2, 1.0),
+            corpse(3, 4.0, DynamicFlags::LOOTABLE, UnitFlags::default()),
+            object(4, 2.0, 1731, GameObjectType::Chest),
+        ]);
+        let (selected, verdicts) = run(&world, in_world(), &Settings::default());
+        assert_eq!(selected, Some(3));
+        assert_eq!(
+            verdicts[0],
+            (PLAYER_GUID, Verdict::WrongType(ObjectType::Player))
+        );
+        assert_eq!(
+            verdicts[2],
+            (
+                3,
+                Verdict::Candidate {
+                    tier: Tier::Lootable,
+                    distance: 4.0,
+                    score: 4.0
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_select_explains_rejections() {
+        let mut pet = alive(5, 1.0);
+        pet.unit.summoned_by = PLAYER_GUID;
+        let mut locked = object(6, 1.0, 1731, GameObjectType::Chest);
+        locked.gameobject.flags = GameObjectFlags::LOCKED;
+        let world = TestWorld(vec![
+            player(),
+            alive(2, 6.0),
+            corpse(3, 1.0, DynamicFlags::default(), UnitFlags::default()),
+            object(4, 1.0, config::DEFAULT_BLACKLIST[0], GameObjectType::Chest),
+            pet,
+            locked,
+            object(7, 1.0, 35591, GameObjectType::FishingNode),
+        ]);
+        let (selected, verdicts) = run(&world, in_world(), &Settings::default());
+        assert_eq!(selected, None);
+        let verdicts: Vec<Verdict> = verdicts.into_iter().skip(1).map(|(_, v)| v).collect();
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::OutOfRange,
+                Verdict::DeadEmpty,
+                Verdict::Blacklisted,
+                Verdict::SummonedByPlayer,
+                Verdict::NotInteractable,
+                Verdict::OtherFishingBobber,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_ghost_only_alive_units() {
+        let world = TestWorld(vec![
+            player(),
+            object(2, 1.0, 1731, GameObjectType::Chest),
+            alive(3, 4.0),
+        ]);
+        let ghost = WorldState {
+            ghost: true,
+            ..in_world()
+        };
+        assert_eq!(run(&world, ghost, &Settings::default()).0, Some(3));
+    }
+
+    #[test]
+    fn test_select_name_filter() {
+        let world = TestWorld(vec![player(), alive(2, 1.0), alive(3, 2.0)]);
+        let settings = Settings {
+            filter: NameFilter::parse("", "Object 2"),
+            ..Settings::default()
+        };
+        let (selected, verdicts) = run(&world, in_world(), &settings);
+        assert_eq!(selected, Some(3));
+        assert_eq!(verdicts[1], (2, Verdict::FilteredByName));
+    }
+
+    #[test]
+    fn test_select_retries_without_hidden_candidates() {
+        let mut hidden = alive(2, 1.0);
+        hidden.visible = false;
+        let world = TestWorld(vec![player(), hidden, alive(3, 2.0)]);
+        let settings = Settings {
+            line_of_sight: LineOfSightConfig {
+                enabled: true,
+                max_checks: 3,
+            },
+            ..Settings::default()
+        };
+        let (selected, verdicts) = run(&world, in_world(), &settings);
+        assert_eq!(selected, Some(3));
+        // One walk: every object once, plus the failed check
+        assert_eq!(verdicts.len(), world.0.len() + 1);
+        let hidden: Vec<Verdict> = verdicts
+            .iter()
+            .filter(|(guid, _)| *guid == 2)
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(hidden.len(), 2);
+        assert_eq!(hidden[1], Verdict::NoLineOfSight { distance: 1.0 });
+    }
+
+    #[test]
+    fn test_select_line_of_sight_gives_up_after_max_checks() {
+        let mut first = alive(2, 1.0);
+        first.visible = false;
+        let world = TestWorld(vec![player(), first, alive(3, 2.0)]);
+        let settings = Settings {
+            line_of_sight: LineOfSightConfig {
+                enabled: true,
+                max_checks: 1,
+            },
+            ..Settings::default()
+        };
+        assert_eq!(run(&world, in_world(), &settings).0, None);
+    }
+
+    #[test]
+    fn test_select_line_of_sight_traces_in_priority_order() {
+        let mut far_loot = corpse(2, 3.0, DynamicFlags::LOOTABLE, UnitFlags::default());
+        far_loot.visible = false;
+        let mut near_loot = corpse(3, 1.0, DynamicFlags::LOOTABLE, UnitFlags::default());
+        near_loot.visible = false;
+        let mut chest = object(4, 2.0, 1731, GameObjectType::Chest);
+        chest.visible = false;
+        let world = TestWorld(vec![
+            player(),
+            far_loot,
+            near_loot,
+            chest,
+            object(5, 4.0, 1731, GameObjectType::Chest),
+            alive(6, 0.5),
+        ]);
+        let settings = Settings {
+            line_of_sight: LineOfSightConfig {
+                enabled: true,
+                max_checks: 4,
+            },
+            ..Settings::default()
+        };
+        let (selected, verdicts) = run(&world, in_world(), &settings);
+        assert_eq!(selected, Some(5));
+        let traced: Vec<u64> = verdicts
+            .iter()
+            .filter(|(_, v)| matches!(v, Verdict::NoLineOfSight { .. }))
+            .map(|(guid, _)| *guid)
+            .collect();
+        assert_eq!(traced, [3, 2, 4]);
+        assert_eq!(verdicts.len(), world.0.len() + traced.len());
+
+        // Stops after max_checks, before reaching the visible chest
+        let settings = Settings {
+            line_of_sight: LineOfSightConfig {
+                enabled: true,
+                max_checks: 3,
+            },
+            ..Settings::default()
+        };
+        assert_eq!(run(&world, in_world(), &settings).0, None);
+    }
+
+    #[test]
+    fn test_settings_from_config() {
+        let config = Config::parse(
+            r#"
+            priority = ["alive", "lootable"]
+
+            [range]
+            distance = 6
+            max_height = 3
+
+            [object_radius]
+            32349 = 2
+            "#,
+        )
+        .unwrap();
+        let settings = Settings::from(&config);
+        assert_eq!(settings.range.max_distance, 6.0);
+        assert_eq!(
+            settings.range.model,
+            RangeModel::Cylindrical { max_height: 3.0 }
+        );
+        assert_eq!(settings.radii.get(32349, 0.0), 2.0);
+        assert_eq!(settings.priority, [Tier::Alive, Tier::Lootable]);
+    }
+}
@@ -0,0 +1,60 @@
+//! Settings in effect
+//!
+//! The values `InteractNearest` selects with, set from `interact.toml` by
+//! `apply` and changed by the `InteractSet*` functions in `script` and by
+//! slash commands. Each setting has its own lock so a reader only blocks
+//! writers of that one setting; `selection::Rules` is built from the read
+//! guards. The blacklist lives in `game` and the trace flag in `log`.
+
+use crate::config::{Config, Tier, DEFAULT_PRIORITY};
+use crate::game::{self, NameFilter};
+use crate::selection::{FacingConfig, LineOfSightConfig, ObjectRadii, RangeConfig, Settings};
+use once_cell::sync::Lazy;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard};
+
+/// Name patterns applied to candidates, set via `InteractSetNameFilters`
+pub static NAME_FILTER: Lazy<RwLock<NameFilter>> = Lazy::new(|| RwLock::new(NameFilter::default()));
+
+/// Range settings, set via `InteractSetRange`
+pub static RANGE: RwLock<RangeConfig> = RwLock::new(RangeConfig::DEFAULT);
+
+/// Per-entry game object radii, set via `InteractSetObjectRadius`
+pub static OBJECT_RADII: Lazy<RwLock<ObjectRadii>> =
+    Lazy::new(|| RwLock::new(ObjectRadii::default()));
+
+/// Facing preferences, set via `InteractSetFacing`
+pub static FACING: Lazy<RwLock<FacingConfig>> = Lazy::new(|| RwLock::new(FacingConfig::default()));
+
+/// Line of sight settings, set via `InteractSetLineOfSight`
+pub static LINE_OF_SIGHT: RwLock<LineOfSightConfig> = RwLock::new(LineOfSightConfig::DEFAULT);
+
+/// Tier selection order, set via `InteractSetPriority`
+pub static PRIORITY: Lazy<RwLock<Vec<Tier>>> = Lazy::new(|| RwLock::new(DEFAULT_PRIORITY.to_vec()));
+
+/// Get read access to the active name filter
+pub fn name_filter() -> RwLockReadGuard<'static, NameFilter> {
+    NAME_FILTER.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Replace every setting with the values from `config`
+pub fn apply(config: &Config) {
+    let Settings {
+        range,
+        radii,
+        facing,
+        line_of_sight,
+        filter,
+        priority,
+    } = Settings::from(config);
+    *RANGE.write().unwrap_or_else(PoisonError::into_inner) = range;
+    *OBJECT_RADII.write().unwrap_or_else(PoisonError::into_inner) = radii;
+    *FACING.write().unwrap_or_else(PoisonError::into_inner) = facing;
+    *LINE_OF_SIGHT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = line_of_sight;
+    *NAME_FILTER.write().unwrap_or_else(PoisonError::into_inner) = filter;
+    *PRIORITY.write().unwrap_or_else(PoisonError::into_inner) = priority;
+
+    game::set_blacklist(&config.blacklist.objects);
+    crate::log::set_trace(config.log.trace);
+}
@@ -0,0 +1,402 @@
+//! World snapshots for `InteractCaptureSnapshot()`
+//!
+//! A snapshot records the player, their state and every visible object with
+//! the descriptor fields the selection reads. It is written as JSON in the
+//! `interact-sim` fixture format, so a bad pick captured in the client can be
+//! attached to a bug report and replayed offline. Objects are read through
+//! `selection::World`, the same view of the client `InteractNearest` uses.
+
+use crate::errors::SnapshotError;
+use crate::game::{C3Vector, ObjectType, WorldState};
+use crate::selection::{PlayerView, World};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Version of the snapshot layout, written as `version`. Bump it when a
+/// field is removed or changes meaning.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Start of snapshot file names; the capture time follows
+pub const FILE_PREFIX: &str = "interact_snapshot_";
+
+// =============================================================================
+// Snapshot
+// =============================================================================
+
+/// Everything `InteractNearest` would look at, at one moment
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub version: u32,
+    /// Who captured the snapshot, e.g. DLL version and client build
+    pub description: String,
+    pub player: PlayerRecord,
+    pub state: StateRecord,
+    pub objects: Vec<ObjectRecord>,
+}
+
+/// The local player
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerRecord {
+    pub guid: u64,
+    /// `[x, y, z]`
+    pub position: [f32; 3],
+    /// Radians, as `GetPlayerFacing()` returns it
+    pub facing: f32,
+}
+
+/// Player state flags, as `WorldState` without `in_world`
+#[derive(Debug, Clone, Copy, Serialize)]
+#[allow(clippy::struct_excessive_bools)] // Mirrors WorldState
+pub struct StateRecord {
+    pub loading: bool,
+    pub dead: bool,
+    pub ghost: bool,
+    pub on_taxi: bool,
+    pub in_combat: bool,
+    pub casting: bool,
+}
+
+/// A visible object. Units and game objects add their descriptor fields;
+/// flags are written as hex strings.
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectRecord {
+    pub guid: u64,
+    /// `unit`, `player`, `gameobject`, `item`, `container`, `dynamicobject`
+    /// or `corpse`
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Creature or game object entry ID
+    pub entry: u32,
+    pub position: [f32; 3],
+    /// Whether `CWorld::Intersect` found nothing between the player and
+    /// the object; only checked for units and game objects
+    pub line_of_sight: bool,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<UnitRecord>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub gameobject: Option<GameObjectRecord>,
+}
+
+/// Unit descriptor fields
+#[derive(Debug, Clone, Serialize)]
+pub struct UnitRecord {
+    pub health: i32,
+    pub max_health: u32,
+    pub level: u32,
+    pub faction_template: u32,
+    pub flags: String,
+    pub dynamic_flags: String,
+    pub npc_flags: String,
+    pub summoned_by: u64,
+    pub target: u64,
+    pub creature_type: u32,
+    pub creature_family: u32,
+}
+
+/// Game object descriptor fields
+#[derive(Debug, Clone, Serialize)]
+pub struct GameObjectRecord {
+    pub go_type: u32,
+    pub flags: String,
+    pub state: u32,
+    pub display_id: u32,
+    pub created_by: u64,
+}
+
+impl Snapshot {
+    /// Record the player and every object `world` reports
+    pub fn capture<W: World>(
+        world: &W,
+        player: &PlayerView,
+        state: WorldState,
+        description: String,
+    ) -> Self {
+        let objects = world
+            .objects()
+            .map(|(guid, object)| ObjectRecord::capture(world, player, guid, object))
+            .collect();
+
+        Self {
+            version: SNAPSHOT_VERSION,
+            description,
+            player: PlayerRecord {
+                guid: player.guid,
+                position: coordinates(player.position),
+                facing: player.facing,
+            },
+            state: StateRecord::from(state),
+            objects,
+        }
+    }
+
+    /// The snapshot as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string_pretty(self).map_err(|e| SnapshotError::Serialize(e.to_string()))
+    }
+
+    /// Write the snapshot to `dir`, creating it if needed, as
+    /// `interact_snapshot_<stamp>.json`. Returns the path written.
+    pub fn write(&self, dir: &Path, stamp: &str) -> Result<PathBuf, SnapshotError> {
+        let json = self.to_json()?;
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{FILE_PREFIX}{stamp}.json"));
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+}
+
+impl ObjectRecord {
+    fn capture<W: World>(world: &W, player: &PlayerView, guid: u64, object: W::Object) -> Self {
+        let obj_type = world.object_type(object);
+        let unit = (obj_type == ObjectType::Unit).then(|| {
+            let descriptor = world.unit_descriptor(object);
+            UnitRecord {
+                health: descriptor.health,
+                max_health: descriptor.max_health,
+                level: descriptor.level,
+                faction_template: descriptor.faction_template,
+                flags: hex(descriptor.flags.0),
+                dynamic_flags: hex(descriptor.dynamic_flags.0),
+                npc_flags: hex(descriptor.npc_flags.0),
+                summoned_by: descriptor.summoned_by,
+                target: descriptor.target,
+                creature_type: descriptor.creature_type as u32,
+                creature_family: descriptor.creature_family,
+            }
+        });
+        let gameobject = (obj_type == ObjectType::GameObject).then(|| {
+            let descriptor = world.gameobject_descriptor(object);
+            GameObjectRecord {
+                go_type: descriptor.go_type as u32,
+                flags: hex(descriptor.flags.0),
+                state: descriptor.state as u32,
+                display_id: descriptor.display_id,
+                created_by: descriptor.created_by,
+            }
+        });
+        let line_of_sight = !matches!(obj_type, ObjectType::Unit | ObjectType::GameObject)
+            || world.in_line_of_sight(&player.position, object);
+
+        Self {
+            guid,
+            kind: type_name(obj_type),
+            name: world.name(object),
+            entry: world.entry_id(object),
+            position: coordinates(world.position(object)),
+            line_of_sight,
+            unit,
+            gameobject,
+        }
+    }
+}
+
+impl From<WorldState> for StateRecord {
+    fn from(state: WorldState) -> Self {
+        Self {
+            loading: state.loading,
+            dead: state.dead,
+            ghost: state.ghost,
+            on_taxi: state.on_taxi,
+            in_combat: state.in_combat,
+            casting: state.casting,
+        }
+    }
+}
+
+fn coordinates(position: C3Vector) -> [f32; 3] {
+    [position.x, position.y, position.z]
+}
+
+fn hex(flags: u32) -> String {
+    format!("{flags:#010x}")
+}
+
+/// Fixture name of an object type
+fn type_name(obj_type: ObjectType) -> &'static str {
+    match obj_type {
+        ObjectType::None => "none",
+        ObjectType::Item => "item",
+        ObjectType::Container => "container",
+        ObjectType::Unit => "unit",
+        ObjectType::Player => "player",
+        ObjectType::GameObject => "gameobject",
+        ObjectType::DynamicObject => "dynamicobject",
+        ObjectType::Corpse => "corpse",
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descriptors::{
+        DynamicFlags, GameObjectDescriptor, GameObjectFlags, GameObjectType, UnitDescriptor,
+        UnitFlags,
+    };
+
+    /// A player, a lootable boar behind a wall, a chest and a corpse
+    struct TestWorld;
+
+    impl World for TestWorld {
+        type Object = u64;
+
+        fn objects(&self) -> impl Iterator<Item = (u64, u64)> {
+            [1, 2, 3, 4].into_iter().map(|guid| (guid, guid))
+        }
+
+        fn object_type(&self, object: u64) -> ObjectType {
+            match object {
+                1 => ObjectType::Player,
+                2 => ObjectType::Unit,
+                3 => ObjectType::GameObject,
+                _ => ObjectType::Corpse,
+            }
+        }
+
+        fn position(&self, object: u64) -> C3Vector {
+            C3Vector {
+                x: object as f32,
+                y: 0.5,
+                z: -1.0,
+            }
+        }
+
+        fn name(&self, object: u64) -> Option<String> {
+            match object {
+                2 => Some("Boar".to_owned()),
+                3 => Some("Chest".to_owned()),
+                _ => None,
+            }
+        }
+
+        fn summoned_by(&self, _object: u64) -> u64 {
+            0
+        }
+
+        fn type_of_guid(&self, guid: u64) -> Option<ObjectType> {
+            Some(self.object_type(guid))
+        }
+
+        fn unit_descriptor(&self, _object: u64) -> UnitDescriptor {
+            UnitDescriptor {
+                health: 0,
+                level: 5,
+                flags: UnitFlags::SKINNABLE,
+                dynamic_flags: DynamicFlags::LOOTABLE,
+                ..UnitDescriptor::default()
+            }
+        }
+
+        fn entry_id(&self, object: u64) -> u32 {
+            match object {
+                2 => 113,
+                3 => 1617,
+                _ => 0,
+            }
+        }
+
+        fn gameobject_descriptor(&self, _object: u64) -> GameObjectDescriptor {
+            GameObjectDescriptor {
+                created_by: 1,
+                flags: GameObjectFlags::LOCKED,
+                go_type: GameObjectType::Chest,
+                ..GameObjectDescriptor::default()
+            }
+        }
+
+        fn in_line_of_sight(&self, _from: &C3Vector, object: u64) -> bool {
+            object != 2
+        }
+    }
+
+    fn capture() -> Snapshot {
+        let player = PlayerView {
+            guid: 1,
+            position: C3Vector::default(),
+            facing: 1.5,
+        };
+        let state = WorldState {
+            in_world: true,
+            in_combat: true,
+            ..WorldState::default()
+        };
+        Snapshot::capture(&TestWorld, &player, state, "test".to_owned())
+    }
+
+    // -------------------------------------------------------------------------
+    // Capture tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_capture_records_every_object() {
+        let snapshot = capture();
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert!(snapshot.state.in_combat);
+        let kinds: Vec<&str> = snapshot.objects.iter().map(|o| o.kind).collect();
+        assert_eq!(kinds, ["player", "unit", "gameobject", "corpse"]);
+
+        let boar = &snapshot.objects[1];
+        assert_eq!(boar.entry, 113);
+        assert!(!boar.line_of_sight);
+        assert!(boar.gameobject.is_none());
+        let unit = boar.unit.as_ref().unwrap();
+        assert_eq!(unit.level, 5);
+        assert_eq!(unit.flags, "0x04000000");
+        assert_eq!(unit.dynamic_flags, "0x00000001");
+
+        let chest = &snapshot.objects[2];
+        assert!(chest.line_of_sight);
+        assert!(chest.unit.is_none());
+        let gameobject = chest.gameobject.as_ref().unwrap();
+        assert_eq!(gameobject.go_type, 3);
+        assert_eq!(gameobject.created_by, 1);
+
+        // Line of sight is only checked for objects that can be selected
+        assert!(snapshot.objects[3].line_of_sight);
+    }
+
+    #[test]
+    fn test_json_uses_fixture_field_names() {
+        let json: serde_json::Value = serde_json::from_str(&capture().to_json().unwrap()).unwrap();
+        assert_eq!(json["version"], SNAPSHOT_VERSION);
+        assert_eq!(
+            json["player"]["position"],
+            serde_json::json!([0.0, 0.0, 0.0])
+        );
+        assert_eq!(json["player"]["facing"], 1.5);
+        assert_eq!(json["state"]["in_combat"], true);
+
+        let boar = &json["objects"][1];
+        assert_eq!(boar["type"], "unit");
+        assert_eq!(boar["name"], "Boar");
+        assert_eq!(boar["position"], serde_json::json!([2.0, 0.5, -1.0]));
+        assert_eq!(boar["flags"], "0x04000000");
+        assert!(boar.get("go_type").is_none());
+
+        let chest = &json["objects"][2];
+        assert_eq!(chest["go_type"], 3);
+        assert_eq!(chest["flags"], "0x00000002");
+        assert!(chest.get("health").is_none());
+
+        // Unnamed objects have no name field
+        assert!(json["objects"][3].get("name").is_none());
+    }
+
+    #[test]
+    fn test_write_creates_directory_and_file() {
+        let dir = std::env::temp_dir().join(format!("interact-snapshot-{}", std::process::id()));
+        let path = capture().write(&dir, "20260101_120000").unwrap();
+        assert_eq!(
+            path.file_name().unwrap(),
+            "interact_snapshot_20260101_120000.json"
+        );
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("\"description\": \"test\""));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+//! Compile and link the host Lua interpreter
+
+fn main() {
+    lua_src::Build::new()
+        .build(lua_src::Lua51)
+        .print_cargo_metadata();
+}
@@ -0,0 +1,195 @@
+//! interact-lua-host - Host Lua interpreter for tests
+//!
+//! Builds Lua from source so script functions can be registered and called
+//! from real Lua on the build machine.
+//!
+//! The client runs a Lua 5.0 variant, but this is Lua 5.1: `lua-src` does
+//! not package 5.0. The tests therefore check argument handling and return
+//! values, not that the calls are right for the client. What they cannot
+//! catch:
+//!
+//! - `lua_error`: the host pushes the message and calls the real
+//!   `lua_error`, which raises the value on top of the stack as 5.0 does,
+//!   so the message reaches `pcall` without a position prefix. The client's
+//!   function at `offsets::lua_api::ERROR` takes the message directly and
+//!   is cdecl; that wrapper is only exercised in the client.
+//! - `lua_setfield`, `lua_createtable` and `lua_tolstring` are new in 5.1.
+//!   `LuaApi` only wraps functions 5.0 also has (`settable`, `newtable`,
+//!   `tostring`), and `HostLua` maps them onto the 5.1 ones. Using a
+//!   5.1-only function in `LuaApi` would pass here and fail in the client.
+//! - `lua_type`: the type tags are the same numbers in 5.0 and 5.1, and the
+//!   script functions only compare against `LUA_TNIL`. A check on any other
+//!   tag has to be verified in the client.
+//! - `LUA_GLOBALSINDEX` is -10001 in 5.0 and -10002 here. `register` uses
+//!   the 5.1 value; the DLL registers through `FrameScript_RegisterFunction`
+//!   and never touches it.
+//! - Calling conventions: the client's functions are fastcall, the host's
+//!   are C. `ClientLua` declares them; nothing here checks it.
+//! - Lua snippets in the tests run as 5.1. They may use `select('#', ...)`,
+//!   which 5.0 does not have; the addon must stick to Lua 5.0 (`table.getn`,
+//!   `string.gfind`).
+
+#![warn(clippy::all)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)] // Test support, panics are failures
+#![allow(clippy::must_use_candidate)]
+
+use std::ffi::{c_char, c_void, CStr};
+
+/// Raw Lua C API, as exported by the host build
+pub mod ffi {
+    use std::ffi::{c_char, c_int, c_void};
+
+    pub type LuaState = *mut c_void;
+    pub type LuaCFunction = unsafe extern "C" fn(LuaState) -> c_int;
+
+    /// Pseudo-index of the globals table
+    pub const LUA_GLOBALSINDEX: c_int = -10002;
+
+    extern "C" {
+        pub fn luaL_newstate() -> LuaState;
+        pub fn luaL_openlibs(l: LuaState);
+        pub fn lua_close(l: LuaState);
+        pub fn luaL_loadbuffer(
+            l: LuaState,
+            buff: *const c_char,
+            size: usize,
+            name: *const c_char,
+        ) -> c_int;
+        pub fn lua_pcall(l: LuaState, nargs: c_int, nresults: c_int, errfunc: c_int) -> c_int;
+        pub fn lua_pushcclosure(l: LuaState, f: LuaCFunction, n: c_int);
+        pub fn lua_setfield(l: LuaState, idx: c_int, k: *const c_char);
+
+        pub fn lua_gettop(l: LuaState) -> c_int;
+        pub fn lua_settop(l: LuaState, idx: c_int);
+        pub fn lua_type(l: LuaState, idx: c_int) -> c_int;
+        pub fn lua_isnumber(l: LuaState, idx: c_int) -> c_int;
+        pub fn lua_isstring(l: LuaState, idx: c_int) -> c_int;
+        pub fn lua_tonumber(l: LuaState, idx: c_int) -> f64;
+        pub fn lua_tolstring(l: LuaState, idx: c_int, len: *mut usize) -> *const c_char;
+        pub fn lua_pushnumber(l: LuaState, n: f64);
+        pub fn lua_pushstring(l: LuaState, s: *const c_char);
+        pub fn lua_pushnil(l: LuaState);
+        pub fn lua_pushboolean(l: LuaState, b: c_int);
+        pub fn lua_createtable(l: LuaState, narr: c_int, nrec: c_int);
+        pub fn lua_settable(l: LuaState, idx: c_int);
+        pub fn lua_error(l: LuaState) -> c_int;
+    }
+}
+
+/// An owned Lua state with the standard libraries loaded
+pub struct Lua {
+    state: *mut c_void,
+}
+
+impl Lua {
+    pub fn new() -> Self {
+        // SAFETY: luaL_newstate returns a fresh state or null on allocation
+        // failure, which is checked before use
+        let state = unsafe { ffi::luaL_newstate() };
+        assert!(!state.is_null(), "Failed to create Lua state");
+        unsafe { ffi::luaL_openlibs(state) };
+        Self { state }
+    }
+
+    /// Raw state pointer, as passed to C functions
+    pub fn state(&self) -> ffi::LuaState {
+        self.state
+    }
+
+    /// Register a global C function, like `FrameScript_RegisterFunction`
+    pub fn register(&self, name: &CStr, function: ffi::LuaCFunction) {
+        // SAFETY: state is valid for the lifetime of self
+        unsafe {
+            ffi::lua_pushcclosure(self.state, function, 0);
+            ffi::lua_setfield(self.state, ffi::LUA_GLOBALSINDEX, name.as_ptr());
+        }
+    }
+
+    /// Run a chunk of Lua
+    ///
+    /// # Errors
+    /// Returns the Lua error message if the chunk fails to load or run
+    pub fn exec(&self, code: &str) -> Result<(), String> {
+        // SAFETY: state is valid; the chunk and its results are popped
+        // before returning
+        unsafe {
+            let status = ffi::luaL_loadbuffer(
+                self.state,
+                code.as_ptr().cast::<c_char>(),
+                code.len(),
+                c"test".as_ptr(),
+            );
+            let status = if status == 0 {
+                ffi::lua_pcall(self.state, 0, 0, 0)
+            } else {
+                status
+            };
+            if status == 0 {
+                return Ok(());
+            }
+            let message = error_message(self.state);
+            ffi::lua_settop(self.state, 0);
+            Err(message)
+        }
+    }
+}
+
+impl Default for Lua {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Lua {
+    fn drop(&mut self) {
+        // SAFETY: the state is owned and not used after this
+        unsafe { ffi::lua_close(self.state) };
+    }
+}
+
+/// Error message on top of the stack
+unsafe fn error_message(l: ffi::LuaState) -> String {
+    let ptr = ffi::lua_tolstring(l, -1, std::ptr::null_mut());
+    if ptr.is_null() {
+        return "<non-string error>".to_owned();
+    }
+    CStr::from_ptr(ptr).to_string_lossy().into_owned()
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::c_int;
+
+    unsafe extern "C" fn add(l: ffi::LuaState) -> c_int {
+        let sum = ffi::lua_tonumber(l, 1) + ffi::lua_tonumber(l, 2);
+        ffi::lua_pushnumber(l, sum);
+        1
+    }
+
+    #[test]
+    fn test_exec_runs_lua() {
+        let lua = Lua::new();
+        assert_eq!(lua.exec("assert(1 + 1 == 2)"), Ok(()));
+    }
+
+    #[test]
+    fn test_exec_reports_errors() {
+        let lua = Lua::new();
+        let err = lua.exec("error('boom', 0)").unwrap_err();
+        assert_eq!(err, "boom");
+        assert!(lua.exec("this is not lua").is_err());
+    }
+
+    #[test]
+    fn test_register_function() {
+        let lua = Lua::new();
+        lua.register(c"Add", add);
+        assert_eq!(lua.exec("assert(Add(2, 3) == 5)"), Ok(()));
+    }
+}
@@ -0,0 +1,20 @@
+//! Error types for interact-sim
+
+use interact_core::ConfigError;
+use thiserror::Error;
+
+/// Errors that can occur while loading a fixture
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    /// The file could not be read
+    #[error("Failed to read fixture: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The file is not valid JSON or TOML or does not match the fixture layout
+    #[error("Failed to parse fixture: {0}")]
+    Parse(String),
+
+    /// The fixture's `config` section parsed but is invalid
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+}
@@ -0,0 +1,459 @@
+//! World fixtures
+//!
+//! A fixture describes what the client would see when `InteractNearest` is
+//! pressed: the player, the visible objects and optionally the settings from
+//! `interact.toml`. Fixtures are JSON, or TOML when the file name ends in
+//! `.toml`; both use the same field names as `interact.toml`.
+//!
+//! Snapshots written by `InteractCaptureSnapshot()` are fixtures too: they add
+//! a `version` and the descriptor fields the selection does not read.
+
+use crate::errors::FixtureError;
+use interact_core::config::Config;
+use interact_core::descriptors::{
+    CreatureType, DynamicFlags, GameObjectDescriptor, GameObjectFlags, GameObjectState,
+    GameObjectType, NpcFlags, UnitDescriptor, UnitFlags,
+};
+use interact_core::game::{C3Vector, ObjectType, WorldState};
+use interact_core::selection::{PlayerView, World};
+use interact_core::snapshot::SNAPSHOT_VERSION;
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+
+// =============================================================================
+// Fixture
+// =============================================================================
+
+/// A world to run the selection against
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    /// Snapshot layout version; hand-written fixtures leave it out
+    pub version: Option<u32>,
+    /// What the fixture reproduces, printed with the result
+    #[serde(default)]
+    pub description: String,
+    pub player: Player,
+    #[serde(default)]
+    pub state: State,
+    /// Same contents as `interact.toml`; the defaults if missing
+    pub config: Option<Config>,
+    #[serde(default)]
+    pub objects: Vec<Object>,
+    /// GUID that must be selected, or 0 if nothing may be
+    pub expect: Option<u64>,
+}
+
+/// The local player
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Player {
+    #[serde(default = "default_player_guid")]
+    pub guid: u64,
+    #[serde(deserialize_with = "position")]
+    pub position: C3Vector,
+    /// Facing in radians, as `GetPlayerFacing()` returns it
+    #[serde(default)]
+    pub facing: f32,
+}
+
+/// Player state flags; everything is off by default
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)] // Mirrors WorldState
+pub struct State {
+    pub loading: bool,
+    pub dead: bool,
+    pub ghost: bool,
+    pub on_taxi: bool,
+    pub in_combat: bool,
+    pub casting: bool,
+}
+
+/// Types of visible objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Unit,
+    Player,
+    GameObject,
+    Item,
+    Container,
+    DynamicObject,
+    Corpse,
+}
+
+/// A visible object. `flags` are unit flags for units and game object flags
+/// for game objects; flag fields take a number or a hex string.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Object {
+    pub guid: u64,
+    #[serde(rename = "type")]
+    pub kind: Kind,
+    pub name: Option<String>,
+    /// Creature or game object entry ID
+    #[serde(default)]
+    pub entry: u32,
+    #[serde(deserialize_with = "position")]
+    pub position: C3Vector,
+    #[serde(default = "default_health")]
+    pub health: i32,
+    #[serde(default, deserialize_with = "flags")]
+    pub flags: u32,
+    #[serde(default, deserialize_with = "flags")]
+    pub dynamic_flags: u32,
+    #[serde(default)]
+    pub summoned_by: u64,
+    /// Game object type, by name (`"chest"`) or number (`3`)
+    #[serde(default, deserialize_with = "gameobject_type")]
+    pub go_type: GameObjectType,
+    #[serde(default)]
+    pub created_by: u64,
+    /// Whether `CWorld::Intersect` would find nothing in the way
+    #[serde(default = "default_line_of_sight")]
+    pub line_of_sight: bool,
+
+    // Other descriptor fields recorded by snapshots
+    #[serde(default)]
+    pub max_health: u32,
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub faction_template: u32,
+    #[serde(default, deserialize_with = "flags")]
+    pub npc_flags: u32,
+    #[serde(default)]
+    pub target: u64,
+    #[serde(default)]
+    pub creature_type: u32,
+    #[serde(default)]
+    pub creature_family: u32,
+    #[serde(default)]
+    pub state: u32,
+    #[serde(default)]
+    pub display_id: u32,
+}
+
+fn default_player_guid() -> u64 {
+    1
+}
+
+fn default_health() -> i32 {
+    100
+}
+
+fn default_line_of_sight() -> bool {
+    true
+}
+
+impl Fixture {
+    /// Parse a fixture, as TOML if `toml` is set and JSON otherwise
+    pub fn parse(text: &str, toml: bool) -> Result<Self, FixtureError> {
+        let fixture: Self = if toml {
+            toml::from_str(text).map_err(|e| FixtureError::Parse(e.message().to_owned()))?
+        } else {
+            serde_json::from_str(text).map_err(|e| FixtureError::Parse(e.to_string()))?
+        };
+        if let Some(version) = fixture.version.filter(|v| *v > SNAPSHOT_VERSION) {
+            return Err(FixtureError::Parse(format!(
+                "snapshot version {version} is newer than this tool ({SNAPSHOT_VERSION})"
+            )));
+        }
+        if let Some(config) = &fixture.config {
+            config.validate()?;
+        }
+        Ok(fixture)
+    }
+
+    /// Read and parse a fixture file
+    pub fn load(path: &Path) -> Result<Self, FixtureError> {
+        let text = std::fs::read_to_string(path)?;
+        let toml = path.extension().is_some_and(|ext| ext == "toml");
+        Self::parse(&text, toml)
+    }
+
+    /// Player state as the DLL would read it
+    pub fn world_state(&self) -> WorldState {
+        WorldState {
+            in_world: true,
+            loading: self.state.loading,
+            dead: self.state.dead,
+            ghost: self.state.ghost,
+            on_taxi: self.state.on_taxi,
+            in_combat: self.state.in_combat,
+            casting: self.state.casting,
+        }
+    }
+
+    pub fn player_view(&self) -> PlayerView {
+        PlayerView {
+            guid: self.player.guid,
+            position: self.player.position,
+            facing: self.player.facing,
+        }
+    }
+}
+
+impl Kind {
+    pub fn object_type(self) -> ObjectType {
+        match self {
+            Self::Unit => ObjectType::Unit,
+            Self::Player => ObjectType::Player,
+            Self::GameObject => ObjectType::GameObject,
+            Self::Item => ObjectType::Item,
+            Self::Container => ObjectType::Container,
+            Self::DynamicObject => ObjectType::DynamicObject,
+            Self::Corpse => ObjectType::Corpse,
+        }
+    }
+}
+
+// =============================================================================
+// World
+// =============================================================================
+
+/// Objects are referred to by their index in `objects`
+impl World for Fixture {
+    type Object = usize;
+
+    fn objects(&self) -> impl Iterator<Item = (u64, usize)> {
+        self.objects
+            .iter()
+            .enumerate()
+            .map(|(i, object)| (object.guid, i))
+    }
+
+    fn object_type(&self, object: usize) -> ObjectType {
+        self.objects[object].kind.object_type()
+    }
+
+    fn position(&self, object: usize) -> C3Vector {
+        self.objects[object].position
+    }
+
+    fn name(&self, object: usize) -> Option<String> {
+        self.objects[object].name.clone()
+    }
+
+    fn summoned_by(&self, object: usize) -> u64 {
+        self.objects[object].summoned_by
+    }
+
+    fn type_of_guid(&self, guid: u64) -> Option<ObjectType> {
+        if guid == self.player.guid {
+            return Some(ObjectType::Player);
+        }
+        self.objects
+            .iter()
+            .find(|object| object.guid == guid)
+            .map(|object| object.kind.object_type())
+    }
+
+    fn unit_descriptor(&self, object: usize) -> UnitDescriptor {
+        let object = &self.objects[object];
+        UnitDescriptor {
+            summoned_by: object.summoned_by,
+            target: object.target,
+            health: object.health,
+            max_health: object.max_health,
+            level: object.level,
+            faction_template: object.faction_template,
+            flags: UnitFlags(object.flags),
+            dynamic_flags: DynamicFlags(object.dynamic_flags),
+            npc_flags: NpcFlags(object.npc_flags),
+            creature_type: CreatureType::from(object.creature_type),
+            creature_family: object.creature_family,
+        }
+    }
+
+    fn entry_id(&self, object: usize) -> u32 {
+        self.objects[object].entry
+    }
+
+    fn gameobject_descriptor(&self, object: usize) -> GameObjectDescriptor {
+        let object = &self.objects[object];
+        GameObjectDescriptor {
+            created_by: object.created_by,
+            display_id: object.display_id,
+            flags: GameObjectFlags(object.flags),
+            state: GameObjectState::from(object.state),
+            go_type: object.go_type,
+        }
+    }
+
+    fn in_line_of_sight(&self, _from: &C3Vector, object: usize) -> bool {
+        self.objects[object].line_of_sight
+    }
+}
+
+// =============================================================================
+// Field formats
+// =============================================================================
+
+/// `[x, y, z]`
+fn position<'de, D: Deserializer<'de>>(deserializer: D) -> Result<C3Vector, D::Error> {
+    let [x, y, z] = <[f32; 3]>::deserialize(deserializer)?;
+    Ok(C3Vector { x, y, z })
+}
+
+/// A number, or a hex string such as `"0x04000000"`
+fn flags<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flags {
+        Number(u32),
+        Text(String),
+    }
+
+    match Flags::deserialize(deserializer)? {
+        Flags::Number(value) => Ok(value),
+        Flags::Text(text) => {
+            let digits = text
+                .strip_prefix("0x")
+                .or_else(|| text.strip_prefix("0X"))
+                .ok_or_else(|| serde::de::Error::custom(format!("'{text}' is not a hex number")))?;
+            u32::from_str_radix(digits, 16)
+                .map_err(|_| serde::de::Error::custom(format!("'{text}' is not a hex number")))
+        }
+    }
+}
+
+/// A `GameObjectType` name, ignoring case and underscores, or its number
+fn gameobject_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<GameObjectType, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum GoType {
+        Number(u32),
+        Name(String),
+    }
+
+    match GoType::deserialize(deserializer)? {
+        GoType::Number(value) => Ok(GameObjectType::from(value)),
+        GoType::Name(name) => {
+            let wanted = name.replace('_', "");
+            (0..=u32::from(u8::MAX))
+                .map(GameObjectType::from)
+                .take_while(|go_type| *go_type != GameObjectType::Unknown)
+                .find(|go_type| format!("{go_type:?}").eq_ignore_ascii_case(&wanted))
+                .ok_or_else(|| {
+                    serde::de::Error::custom(format!("unknown game object type '{name}'"))
+                })
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)] // Exact float comparisons are intentional in these tests
+
+    use super::*;
+
+    const JSON: &str = r#"{
+        "player": { "position": [1, 2, 3] },
+        "objects": [
+            { "guid": 2, "type": "unit", "position": [1, 2, 4], "health": 0,
+              "dynamic_flags": 1, "flags": "0x04000000" },
+            { "guid": 3, "type": "gameobject", "entry": 1731, "position": [0, 0, 0],
+              "go_type": "fishing_node", "created_by": 1, "line_of_sight": false }
+        ],
+        "expect": 2
+    }"#;
+
+    #[test]
+    fn test_parse_json_fixture() {
+        let fixture = Fixture::parse(JSON, false).unwrap();
+        assert_eq!(fixture.player.guid, 1);
+        let position = fixture.player.position;
+        assert_eq!((position.x, position.y, position.z), (1.0, 2.0, 3.0));
+        assert_eq!(fixture.expect, Some(2));
+        assert!(fixture.config.is_none());
+
+        let unit = fixture.unit_descriptor(0);
+        assert!(unit.is_dead());
+        assert!(unit.is_lootable());
+        assert!(unit.is_skinnable());
+
+        assert_eq!(fixture.object_type(1), ObjectType::GameObject);
+        let gameobject = fixture.gameobject_descriptor(1);
+        assert_eq!(gameobject.go_type, GameObjectType::FishingNode);
+        assert!(gameobject.is_created_by(1));
+        assert!(!fixture.in_line_of_sight(&C3Vector::default(), 1));
+    }
+
+    #[test]
+    fn test_parse_toml_fixture_with_config() {
+        let fixture = Fixture::parse(
+            r#"
+            description = "Ghost at the spirit healer"
+
+            [player]
+            guid = 7
+            position = [0, 0, 0]
+
+            [state]
+            ghost = true
+
+            [config]
+            priority = ["alive"]
+
+            [[objects]]
+            guid = 8
+            type = "unit"
+            position = [2, 0, 0]
+            "#,
+            true,
+        )
+        .unwrap();
+        assert!(!fixture.world_state().can_loot());
+        assert_eq!(fixture.type_of_guid(7), Some(ObjectType::Player));
+        assert_eq!(fixture.type_of_guid(8), Some(ObjectType::Unit));
+        assert_eq!(fixture.type_of_guid(9), None);
+        assert_eq!(fixture.unit_descriptor(0).health, 100);
+    }
+
+    #[test]
+    fn test_parse_gameobject_type_by_number_and_name() {
+        let parse = |go_type: &str| {
+            let text = format!(
+                r#"{{ "player": {{ "position": [0, 0, 0] }}, "objects": [
+                    {{ "guid": 2, "type": "gameobject", "position": [0, 0, 0], "go_type": {go_type} }}
+                ] }}"#
+            );
+            Fixture::parse(&text, false).map(|f| f.objects[0].go_type)
+        };
+        assert_eq!(parse("19").unwrap(), GameObjectType::Mailbox);
+        assert_eq!(parse(r#""Mailbox""#).unwrap(), GameObjectType::Mailbox);
+        assert_eq!(
+            parse(r#""meeting_stone""#).unwrap(),
+            GameObjectType::MeetingStone
+        );
+        assert!(parse(r#""mailbag""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_fixtures() {
+        // Unknown fields, bad flags and invalid config are all errors
+        assert!(
+            Fixture::parse(r#"{ "player": { "position": [0, 0, 0] }, "x": 1 }"#, false).is_err()
+        );
+        assert!(Fixture::parse(
+            r#"{ "player": { "position": [0, 0, 0] }, "objects": [
+                { "guid": 2, "type": "unit", "position": [0, 0, 0], "flags": "12" }
+            ] }"#,
+            false
+        )
+        .is_err());
+        assert!(matches!(
+            Fixture::parse(
+                r#"{ "player": { "position": [0, 0, 0] }, "config": { "range": { "distance": 0 } } }"#,
+                false
+            ),
+            Err(FixtureError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+//! interact-sim - Offline `InteractNearest`
+//!
+//! Loads world fixtures (the player, visible objects and optionally the
+//! `interact.toml` settings) and prints which object `InteractNearest` would
+//! select, with the reason every other object was passed over. Selection
+//! runs through `interact_core::selection::select`, the same code the DLL
+//! uses, so bug reports can be reproduced and priority changes checked on
+//! any platform. Snapshots written by `InteractCaptureSnapshot()` load as
+//! fixtures.
+//!
+//! Usage: `interact-sim [--config interact.toml] <fixture>...`
+//!
+//! `--config` replaces the settings of every fixture. Exits with 1 if a
+//! fixture's `expect` is not met and 2 if a file cannot be loaded.
+
+// =============================================================================
+// Lints
+// =============================================================================
+
+#![warn(clippy::all)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc)] // Errors are described by their types
+#![allow(clippy::must_use_candidate)]
+#![allow(clippy::doc_markdown)] // Technical terms don't need backticks everywhere
+
+mod errors;
+mod fixture;
+mod sim;
+
+use fixture::Fixture;
+use interact_core::config::Config;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: interact-sim [--config interact.toml] <fixture>...";
+
+fn main() -> ExitCode {
+    let mut config_path: Option<PathBuf> = None;
+    let mut fixtures: Vec<PathBuf> = Vec::new();
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            let Some(path) = args.next() else {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            };
+            config_path = Some(path.into());
+        } else if arg == "-h" || arg == "--help" {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        } else {
+            fixtures.push(arg.into());
+        }
+    }
+    if fixtures.is_empty() {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    let config = match config_path.map(|path| Config::load(&path)).transpose() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut failed = false;
+    for (i, path) in fixtures.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("== {}", path.display());
+        let fixture = match Fixture::load(path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                return ExitCode::from(2);
+            }
+        };
+
+        let config = config
+            .clone()
+            .or_else(|| fixture.config.clone())
+            .unwrap_or_default();
+        let outcome = sim::run(&fixture, &config);
+        for line in &outcome.lines {
+            println!("{line}");
+        }
+        match fixture.expect {
+            Some(expect) if !outcome.meets(fixture.expect) => {
+                if expect == 0 {
+                    println!("FAILED: expected nothing to be selected");
+                } else {
+                    println!("FAILED: expected {expect:#x}");
+                }
+                failed = true;
+            }
+            _ => {}
+        }
+    }
+
+    if failed {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
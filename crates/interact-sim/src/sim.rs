@@ -0,0 +1,276 @@
+//! Running the selection against a fixture
+//!
+//! Calls `interact_core::selection::select`, the same code `InteractNearest`
+//! runs in the client, and turns its verdicts into a readable report.
+
+use crate::fixture::{Fixture, Kind, Object};
+use interact_core::config::Config;
+use interact_core::game;
+use interact_core::selection::{self, Settings, Verdict};
+
+/// Result of one simulated `InteractNearest`
+#[derive(Debug)]
+pub struct Outcome {
+    /// GUID of the selected object
+    pub selected: Option<u64>,
+    /// Report to print, one line per object plus a summary
+    pub lines: Vec<String>,
+}
+
+impl Outcome {
+    /// Whether the fixture's `expect`, if any, was met
+    pub fn meets(&self, expect: Option<u64>) -> bool {
+        match expect {
+            None => true,
+            Some(0) => self.selected.is_none(),
+            Some(guid) => self.selected == Some(guid),
+        }
+    }
+}
+
+/// Run the selection with `config`, replacing the global blacklist with the
+/// one it lists
+pub fn run(fixture: &Fixture, config: &Config) -> Outcome {
+    game::set_blacklist(&config.blacklist.objects);
+    let settings = Settings::from(config);
+    let state = fixture.world_state();
+    let player = &fixture.player;
+
+    let mut lines = Vec::new();
+    if !fixture.description.is_empty() {
+        lines.push(fixture.description.clone());
+    }
+    lines.push(format!(
+        "Player {:#x} at ({:.2}, {:.2}, {:.2}), facing {:.2}",
+        player.guid, player.position.x, player.position.y, player.position.z, player.facing
+    ));
+    lines.push(format!(
+        "Priority: {}",
+        settings
+            .priority
+            .iter()
+            .map(|tier| tier.name())
+            .collect::<Vec<_>>()
+            .join(" > ")
+    ));
+
+    if !state.can_interact() {
+        lines.push(format!("InteractNearest skipped: {state:?}"));
+        return Outcome {
+            selected: None,
+            lines,
+        };
+    }
+
+    // Later passes (line of sight retries) overwrite earlier verdicts
+    let mut verdicts: Vec<Option<Verdict>> = vec![None; fixture.objects.len()];
+    let candidate = selection::select(
+        fixture,
+        &fixture.player_view(),
+        state,
+        &settings.rules(),
+        |_, object, verdict| verdicts[object] = Some(verdict),
+    );
+    let selected = candidate.as_ref().map(|candidate| candidate.guid);
+
+    for (object, verdict) in fixture.objects.iter().zip(&verdicts) {
+        let marker = if Some(object.guid) == selected {
+            "=>"
+        } else {
+            "  "
+        };
+        let verdict = verdict.map_or_else(|| "not walked".to_owned(), |v| v.to_string());
+        lines.push(format!(
+            "{marker} {:#06x}  {:<36}  {verdict}",
+            object.guid,
+            describe(object)
+        ));
+    }
+
+    if !state.can_loot() {
+        lines.push("Ghost: only living units can be selected".to_owned());
+    }
+    lines.push(match candidate.and_then(|candidate| candidate.pointer) {
+        Some(index) => {
+            let object = &fixture.objects[index];
+            format!(
+                "Selected: {} (guid {:#x})",
+                object.name.as_deref().unwrap_or("<unnamed>"),
+                object.guid
+            )
+        }
+        None => "Selected: nothing".to_owned(),
+    });
+
+    Outcome { selected, lines }
+}
+
+/// Name and type of an object
+fn describe(object: &Object) -> String {
+    let name = object.name.as_deref().unwrap_or("<unnamed>");
+    match object.kind {
+        Kind::Unit => format!("{name} (unit {})", object.entry),
+        Kind::Player => format!("{name} (player)"),
+        Kind::GameObject => format!("{name} ({:?} {})", object.go_type, object.entry),
+        Kind::Item => format!("{name} (item)"),
+        Kind::Container => format!("{name} (container)"),
+        Kind::DynamicObject => format!("{name} (dynamic object)"),
+        Kind::Corpse => format!("{name} (corpse)"),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interact_core::snapshot::{Snapshot, SNAPSHOT_VERSION};
+    use std::path::Path;
+
+    fn fixture(text: &str) -> Fixture {
+        Fixture::parse(text, false).unwrap()
+    }
+
+    #[test]
+    fn test_run_explains_every_object() {
+        let fixture = fixture(
+            r#"{
+                "player": { "position": [0, 0, 0] },
+                "objects": [
+                    { "guid": 2, "type": "unit", "name": "Boar", "position": [1, 0, 0] },
+                    { "guid": 3, "type": "gameobject", "name": "Peacebloom", "entry": 1617,
+                      "go_type": "chest", "position": [3, 0, 0] },
+                    { "guid": 4, "type": "unit", "name": "Wolf", "position": [9, 0, 0] }
+                ]
+            }"#,
+        );
+        let outcome = run(&fixture, &Config::default());
+        assert_eq!(outcome.selected, Some(3));
+        assert!(outcome.meets(Some(3)));
+        assert!(!outcome.meets(Some(0)));
+
+        let report = outcome.lines.join("\n");
+        assert!(report.contains("Priority: lootable > gameobject > skinnable > alive > service"));
+        assert!(report.contains("Boar (unit 0)"));
+        assert!(report.contains("alive candidate (1.00 yd, score 1.00)"));
+        assert!(report.contains("=> 0x0003  Peacebloom (Chest 1617)"));
+        assert!(report.contains("out of range"));
+        assert!(report.ends_with("Selected: Peacebloom (guid 0x3)"));
+    }
+
+    #[test]
+    fn test_run_skips_when_player_cannot_interact() {
+        let fixture = fixture(
+            r#"{
+                "player": { "position": [0, 0, 0] },
+                "state": { "on_taxi": true },
+                "objects": [{ "guid": 2, "type": "unit", "position": [1, 0, 0] }],
+                "expect": 0
+            }"#,
+        );
+        let outcome = run(&fixture, &Config::default());
+        assert_eq!(outcome.selected, None);
+        assert!(outcome.meets(fixture.expect));
+        assert!(outcome
+            .lines
+            .last()
+            .unwrap()
+            .starts_with("InteractNearest skipped"));
+    }
+
+    #[test]
+    fn test_run_reports_line_of_sight_failures() {
+        let fixture = fixture(
+            r#"{
+                "player": { "position": [0, 0, 0] },
+                "config": { "line_of_sight": { "enabled": true } },
+                "objects": [
+                    { "guid": 2, "type": "unit", "position": [1, 0, 0], "line_of_sight": false },
+                    { "guid": 3, "type": "unit", "position": [2, 0, 0] }
+                ]
+            }"#,
+        );
+        let config = fixture.config.clone().unwrap();
+        let outcome = run(&fixture, &config);
+        assert_eq!(outcome.selected, Some(3));
+        assert!(outcome
+            .lines
+            .iter()
+            .any(|line| line.contains("no line of sight")));
+    }
+
+    /// A snapshot captured from a fixture replays to the same selection
+    #[test]
+    fn test_snapshot_replays_like_the_captured_world() {
+        let original = fixture(
+            r#"{
+                "state": { "in_combat": true },
+                "player": { "guid": 7, "position": [10.5, -3.25, 40], "facing": 2.5 },
+                "objects": [
+                    { "guid": 7, "type": "player", "position": [10.5, -3.25, 40] },
+                    { "guid": 2, "type": "unit", "name": "Boar", "entry": 113,
+                      "position": [11, -3, 40], "health": 0, "dynamic_flags": 1 },
+                    { "guid": 3, "type": "gameobject", "name": "Bobber", "entry": 35591,
+                      "go_type": "fishing_node", "created_by": 7, "position": [12, -3, 40],
+                      "line_of_sight": false },
+                    { "guid": 4, "type": "corpse", "position": [11, -3, 40] }
+                ]
+            }"#,
+        );
+        let config = Config::default();
+        let snapshot = Snapshot::capture(
+            &original,
+            &original.player_view(),
+            original.world_state(),
+            "replay".to_owned(),
+        );
+        let replayed = Fixture::parse(&snapshot.to_json().unwrap(), false).unwrap();
+        assert_eq!(replayed.version, Some(SNAPSHOT_VERSION));
+        assert_eq!(replayed.objects.len(), 4);
+        assert_eq!(replayed.objects[3].kind, Kind::Corpse);
+        assert!(!replayed.objects[2].line_of_sight);
+
+        // Everything after the description matches, the player line included
+        let report = |fixture: &Fixture| {
+            let lines = run(fixture, &config).lines;
+            let start = lines.iter().position(|line| line.starts_with("Player"));
+            lines[start.unwrap()..].to_vec()
+        };
+        let replayed_report = report(&replayed);
+        assert_eq!(report(&original), replayed_report);
+        assert_eq!(replayed_report.last().unwrap(), "Selected: Boar (guid 0x2)");
+    }
+
+    #[test]
+    fn test_newer_snapshot_version_is_rejected() {
+        let text = format!(
+            r#"{{ "version": {}, "player": {{ "position": [0, 0, 0] }} }}"#,
+            SNAPSHOT_VERSION + 1
+        );
+        assert!(Fixture::parse(&text, false).is_err());
+    }
+
+    /// Every fixture shipped in `fixtures/` still selects what it expects
+    #[test]
+    fn test_example_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let fixture = Fixture::load(&path).unwrap();
+            let config = fixture.config.clone().unwrap_or_default();
+            let outcome = run(&fixture, &config);
+            assert!(fixture.expect.is_some(), "{} has no expect", path.display());
+            assert!(
+                outcome.meets(fixture.expect),
+                "{}:\n{}",
+                path.display(),
+                outcome.lines.join("\n")
+            );
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+}
@@ -0,0 +1,87 @@
+//! Self-check report for `InteractDiagnostics()`
+//!
+//! Collects the state of every layer a user's "it doesn't work" could come
+//! from - hooks, Lua, client build, world, object manager and config - into
+//! one report that is both written to the log and returned to Lua.
+//! `interact_core::diagnostics` formats it.
+
+use crate::config;
+use crate::game;
+use crate::{events, hooks, lua, offsets, scheduler};
+pub use interact_core::diagnostics::Report;
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
+// =============================================================================
+// Recorded State
+// =============================================================================
+
+/// Most recent error reported through `record_error`
+static LAST_ERROR: RwLock<Option<String>> = RwLock::new(None);
+
+/// Outcome of the most recent config load
+static CONFIG_STATUS: RwLock<Option<Result<(), String>>> = RwLock::new(None);
+
+/// Log an error and remember it for the next report
+pub fn record_error(message: String) {
+    debug_log!("ERROR: {}", message);
+    *LAST_ERROR.write().unwrap_or_else(PoisonError::into_inner) = Some(message);
+}
+
+/// Remember the outcome of a config load
+pub fn record_config_status(status: Result<(), String>) {
+    *CONFIG_STATUS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(status);
+}
+
+// =============================================================================
+// Report
+// =============================================================================
+
+/// Gather the report from the running client
+pub unsafe fn collect() -> Report {
+    let hooks = hooks::status();
+    let config_path = std::env::current_dir().map_or_else(
+        |_| config::CONFIG_FILE.into(),
+        |dir| dir.join(config::CONFIG_FILE),
+    );
+
+    let mut report = Report {
+        version: hooks::VERSION,
+        hooks_initialized: hooks.initialized,
+        bootstrap_hook_enabled: hooks.bootstrap_enabled,
+        script_hook_enabled: hooks.script_functions_enabled,
+        frame_hook_enabled: hooks.frame_tick_enabled,
+        event_hook_enabled: hooks.events_enabled,
+        scheduled_tasks: scheduler::pending(),
+        event_handlers: events::subscribed(),
+        lua_initialized: lua::try_api().is_ok(),
+        client_build: hooks::client_build(),
+        expected_build: offsets::CLIENT_BUILD,
+        world: game::get_world_state(),
+        config_found: Path::new(config::CONFIG_FILE).exists(),
+        config_path: config_path.display().to_string(),
+        config_status: CONFIG_STATUS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone(),
+        last_error: LAST_ERROR
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone(),
+        ..Report::default()
+    };
+
+    if report.world.in_world {
+        if let Some(manager) = game::ObjectManager::get() {
+            report.object_manager = manager.raw();
+            report.player_guid = manager.player_guid();
+            report.player_pointer =
+                game::get_object_pointer(report.player_guid).map(game::ObjectPtr::raw);
+            report.objects_walked = manager.iter().count();
+        }
+    }
+
+    report
+}
@@ -2,6 +2,7 @@
 //!
 //! Provides strongly-typed errors for better error handling and debugging.
 
+pub use interact_core::{ConfigError, SnapshotError};
 use thiserror::Error;
 
 /// Errors that can occur during hook initialization and operation
@@ -38,6 +39,10 @@ pub enum InteractError {
     /// Lua-related error
     #[error(transparent)]
     Lua(#[from] LuaError),
+
+    /// Config-related error
+    #[error(transparent)]
+    Config(#[from] ConfigError),
 }
 
 impl From<retour::Error> for InteractError {
@@ -0,0 +1,116 @@
+//! Client UI events from the event signal hook
+//!
+//! Wraps `interact_core::events::Dispatcher` for the client's main thread.
+//! The `FrameScript_SignalEvent` hook passes every event to `signal`, which
+//! looks up its name and, for the ones listed in `EventKind` that have a
+//! handler subscribed with `subscribe`, decodes them and calls the handlers.
+
+use crate::{game, offsets};
+use interact_core::events::{parse_args, ClientEvent, Dispatcher, EventKind, HandlerId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr};
+
+/// Stack words forwarded after the format string.
+///
+/// The signal function is variadic, which a detour cannot declare, so the
+/// hook takes a fixed block of words and hands all of them back to the
+/// original. Twelve covers every event the client signals, the longest
+/// being the nine arguments of the chat events.
+pub const VARARG_WORDS: usize = 12;
+
+/// Raw stack words following the format string
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct VarArgs(pub [u32; VARARG_WORDS]);
+
+thread_local! {
+    /// Handlers of the main thread, the only one that signals events
+    static DISPATCHER: RefCell<Dispatcher> = RefCell::new(Dispatcher::new());
+
+    /// Kind of each event ID seen so far, `None` for events the DLL ignores
+    static KINDS: RefCell<HashMap<u32, Option<EventKind>>> = RefCell::new(HashMap::new());
+}
+
+// =============================================================================
+// Subscription
+// =============================================================================
+
+/// Call `handler` for every event of `kind`
+pub fn subscribe(
+    kind: EventKind,
+    name: &'static str,
+    handler: impl FnMut(&ClientEvent) + 'static,
+) -> HandlerId {
+    DISPATCHER.with_borrow_mut(|dispatcher| dispatcher.subscribe(kind, name, handler))
+}
+
+/// Stop calling a handler; `false` if it was not subscribed
+#[allow(dead_code)] // Subscription API for event-driven behaviours
+pub fn unsubscribe(id: HandlerId) -> bool {
+    DISPATCHER.with_borrow_mut(|dispatcher| dispatcher.unsubscribe(id))
+}
+
+/// Number of subscribed handlers, for diagnostics
+pub fn subscribed() -> usize {
+    DISPATCHER.with_borrow(Dispatcher::len)
+}
+
+// =============================================================================
+// Signal
+// =============================================================================
+
+/// Name of an event ID from the client's event table
+unsafe fn event_name(id: u32) -> Option<String> {
+    let count = *(offsets::events::EVENT_COUNT as *const u32);
+    let names = *(offsets::events::EVENT_NAMES as *const u32);
+    if id >= count || names == 0 {
+        return None;
+    }
+    game::read_c_string(*((names + id * 4) as *const u32))
+}
+
+/// Kind of an event ID, looked up by name the first time it is seen
+unsafe fn kind_of(id: u32) -> Option<EventKind> {
+    KINDS.with_borrow_mut(|kinds| {
+        *kinds
+            .entry(id)
+            .or_insert_with(|| event_name(id).as_deref().and_then(EventKind::from_name))
+    })
+}
+
+/// Decode an event and call its handlers. Called from the event signal
+/// hook after the client has handled the event. Events without a subscribed
+/// handler are not decoded.
+///
+/// A handler that panics is unsubscribed and the panic recorded as the last
+/// error; the remaining handlers still run.
+pub unsafe fn signal(id: u32, format: *const c_char, args: &VarArgs) {
+    let Some(kind) = kind_of(id) else {
+        return;
+    };
+    if !DISPATCHER.with_borrow(|dispatcher| dispatcher.wants(kind)) {
+        return;
+    }
+    let format = if format.is_null() {
+        ""
+    } else {
+        CStr::from_ptr(format).to_str().unwrap_or("")
+    };
+    let args = parse_args(format, &args.0, |addr| unsafe { game::read_c_string(addr) });
+    let event = ClientEvent::new(kind, &args);
+    trace_log!("Event {}", event);
+
+    let mut handlers = DISPATCHER.with_borrow_mut(|dispatcher| dispatcher.take(kind));
+    for handler in &mut handlers {
+        if let Err(message) = handler.call(&event) {
+            crate::diagnostics::record_error(format!(
+                "Handler '{}' for {} panicked and was unsubscribed: {message}",
+                handler.name(),
+                kind.name()
+            ));
+            DISPATCHER.with_borrow_mut(|dispatcher| dispatcher.unsubscribe(handler.id()));
+        }
+    }
+    DISPATCHER.with_borrow_mut(|dispatcher| dispatcher.restore(handlers));
+}
@@ -0,0 +1,939 @@
+//! Game interface for WoW 1.12.1
+//!
+//! Provides functions to interact with game objects, units, and the game world.
+//! The plain types (positions, world state, blacklist, name filters) live in
+//! `interact_core::game` and are re-exported here.
+//!
+//! Note on unit position: The original Interact C implementation reads
+//! directly from unit + 0x9B8/0x9BC/0x9C0, which is wrong on boats and
+//! zeppelins. Like UnitXP, we read the movement info via CMovement
+//! (unit + 0x118) and, when the unit is on a transport, convert its
+//! transport-local coordinates to world coordinates.
+
+use crate::descriptors::{
+    CreatureType, DynamicFlags, GameObjectDescriptor, GameObjectFlags, GameObjectState,
+    GameObjectType, NpcFlags, UnitDescriptor, UnitFlags,
+};
+use crate::offsets;
+use interact_core::frame::FrameCache;
+use interact_core::selection::World;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr};
+use std::mem::transmute;
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
+pub use interact_core::game::*;
+
+// =============================================================================
+// Game Function Types
+// =============================================================================
+
+type GetObjectPointerFn = unsafe extern "fastcall" fn(u64) -> u32;
+type SetTargetFn = unsafe extern "stdcall" fn(u64);
+type RightClickFn = unsafe extern "thiscall" fn(u32, i32);
+type WorldIntersectFn = unsafe extern "fastcall" fn(
+    *const C3Vector,
+    *const C3Vector,
+    i32,
+    *mut C3Vector,
+    *mut f32,
+    u32,
+) -> u8;
+
+/// `CWorld::Intersect` flags: terrain, WMO and M2 collision
+const INTERSECT_FLAGS_LOS: u32 = 0x0010_0111;
+
+/// Height above the feet that line of sight is traced from and to, so that
+/// small bumps in the terrain do not block the ray
+const LOS_HEIGHT_OFFSET: f32 = 1.5;
+
+// =============================================================================
+// Memory offset helpers
+// =============================================================================
+
+/// Read a value from a memory address.
+///
+/// # Safety
+/// - `addr` must point to valid, initialized memory within the WoW process
+/// - `addr` must be properly aligned for type `T`
+/// - The memory at `addr` must contain a valid bit pattern for `T`
+/// - The address must be a known WoW 1.12.1.5875 memory location
+#[inline]
+unsafe fn read<T: Copy>(addr: u32) -> T {
+    // SAFETY: Caller guarantees addr is a valid WoW 1.12.1 memory address.
+    // All addresses used are documented in wow_offsets_reference.md and
+    // have been verified against the 1.12.1.5875 client.
+    *(addr as *const T)
+}
+
+/// Read a value at base + offset.
+///
+/// # Safety
+/// - `base + offset` must not overflow
+/// - The resulting address must point to valid, initialized memory
+/// - The address must be properly aligned for type `T`
+/// - Used for reading fields from WoW's internal object structures
+#[inline]
+unsafe fn read_offset<T: Copy>(base: u32, offset: u32) -> T {
+    // SAFETY: Caller guarantees base is a valid object pointer obtained from
+    // the game's object manager, and offset is a documented field offset.
+    // Object structure offsets are from wow_offsets_reference.md.
+    read(base + offset)
+}
+
+// =============================================================================
+// Game State
+// =============================================================================
+
+/// Check if the player is currently in the game world.
+///
+/// Reads the `IsIngame` flag at `0xB4B424`.
+#[inline]
+pub unsafe fn is_in_world() -> bool {
+    // SAFETY: IS_IN_WORLD (0xB4B424) is a static game variable that exists
+    // for the lifetime of the WoW process. See wow_offsets_reference.md: Player.IsIngame
+    read::<u8>(offsets::game::IS_IN_WORLD as u32) != 0
+}
+
+/// Get the visible objects manager pointer.
+///
+/// Returns the base pointer to the object manager at `0xB41414`.
+#[inline]
+pub unsafe fn get_visible_objects() -> u32 {
+    // SAFETY: VISIBLE_OBJECTS (0xB41414) is the static object manager base pointer.
+    // See wow_offsets_reference.md: ObjectManager.ManagerBase
+    read::<u32>(offsets::game::VISIBLE_OBJECTS as u32)
+}
+
+/// Read the local player's world state.
+///
+/// Returns `WorldState::default()` when not in world and
+/// `WorldState::LOADING` while the player object is not yet valid.
+pub unsafe fn get_world_state() -> WorldState {
+    if !is_in_world() {
+        return WorldState::default();
+    }
+
+    let Some(manager) = ObjectManager::get() else {
+        return WorldState::LOADING;
+    };
+
+    let player_guid = manager.player_guid();
+    if player_guid == 0 {
+        return WorldState::LOADING;
+    }
+
+    let Some(player) = get_object_pointer(player_guid).and_then(|p| p.as_player()) else {
+        return WorldState::LOADING;
+    };
+    let unit = player.unit();
+
+    WorldState::from_player_fields(
+        unit.health(),
+        unit.flags(),
+        player.flags(),
+        unit.casting_spell(),
+        unit.channel_spell(),
+    )
+}
+
+// =============================================================================
+// Object Manager
+// =============================================================================
+
+/// Get a pointer to a game object by its GUID.
+///
+/// Calls the game's `GetPtrForGuid` function at `0x464870`.
+/// Returns `None` if the object is not found (null pointer).
+///
+/// The result is an untyped `ObjectPtr`; use `ObjectPtr::cast` (or one of
+/// the `as_*` helpers) to get a pointer whose fields can be read.
+#[inline]
+pub unsafe fn get_object_pointer(guid: u64) -> Option<ObjectPtr> {
+    // SAFETY: GET_OBJECT_POINTER (0x464870) is the game's GetPtrForGuid function.
+    // It safely returns 0 for invalid GUIDs. See wow_offsets_reference.md: Functions.GetPtrForGuid
+    let func: GetObjectPointerFn = transmute(offsets::game::GET_OBJECT_POINTER);
+    NonZeroU32::new(func(guid)).map(ObjectPtr)
+}
+
+/// Get a raw pointer to a game object by its GUID (legacy API).
+///
+/// Prefer `get_object_pointer` which returns a typed `ObjectPtr`.
+/// This function is provided for cases where you need the raw u32 value.
+#[inline]
+#[allow(dead_code)] // Utility function for future use or external callers
+pub unsafe fn get_object_pointer_raw(guid: u64) -> u32 {
+    // SAFETY: This function's safety relies on get_object_pointer.
+    // It provides a raw u32 pointer, returning 0 for None.
+    get_object_pointer(guid).map_or(0, ObjectPtr::raw)
+}
+
+/// The visible objects manager.
+///
+/// Visible objects form a singly linked list starting at `0xAC` of the
+/// manager, linked through `0x3C` of each entry. The list ends at a null
+/// pointer or at an entry with the low bit set: object pointers are always
+/// aligned, so the client uses that bit to tag the end of the list.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectManager(NonZeroU32);
+
+impl ObjectManager {
+    /// Upper bound on list entries walked, in case the list is corrupt or
+    /// circular
+    pub const MAX_ENTRIES: usize = 10_000;
+
+    /// Get the object manager; `None` while it is not set up (not in world
+    /// or loading)
+    #[inline]
+    pub unsafe fn get() -> Option<Self> {
+        NonZeroU32::new(get_visible_objects()).map(Self)
+    }
+
+    /// Get the raw pointer value
+    #[inline]
+    pub fn raw(self) -> u32 {
+        self.0.get()
+    }
+
+    /// Get the player's GUID.
+    ///
+    /// Reads at offset `0xC0` from the object manager base.
+    #[inline]
+    pub unsafe fn player_guid(self) -> u64 {
+        // SAFETY: self is the object manager pointer.
+        // Offset 0xC0 is PlayerGuid. See wow_offsets_reference.md: ObjectManager.PlayerGuid
+        read_offset(self.raw(), 0xC0)
+    }
+
+    /// Walk the list and yield the GUID of every entry, the player
+    /// included, without resolving them
+    pub unsafe fn guids(self) -> GuidIter {
+        GuidIter {
+            // SAFETY: self is the object manager pointer.
+            // Offset 0xAC is FirstObj. See wow_offsets_reference.md: ObjectManager.FirstObj
+            current: read_offset(self.raw(), 0xAC),
+            remaining: Self::MAX_ENTRIES,
+        }
+    }
+
+    /// Iterate over the visible objects and their GUIDs, the player
+    /// included.
+    ///
+    /// Each GUID is resolved with `GetPtrForGuid` and cast to its type;
+    /// entries that do not resolve are skipped. The pointers are only valid
+    /// until the client next updates its objects, so the iterator must be
+    /// used up within the current script call or frame.
+    pub unsafe fn iter(self) -> impl Iterator<Item = (u64, WowObject)> {
+        self.guids().filter_map(|guid| {
+            // SAFETY: GetPtrForGuid returns null for unknown GUIDs
+            unsafe { get_object_pointer(guid) }.map(|pointer| (guid, unsafe { pointer.cast() }))
+        })
+    }
+}
+
+/// GUIDs of the visible objects list, see `ObjectManager::guids`
+#[derive(Debug)]
+pub struct GuidIter {
+    /// Next list entry; 0 or tagged at the end of the list
+    current: u32,
+    /// Entries left before the walk gives up
+    remaining: usize,
+}
+
+impl Iterator for GuidIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.current == 0 || (self.current & 1) != 0 || self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        // SAFETY: current is an untagged entry of the list walked from
+        // ObjectManager::guids. Offset 0x30 is CurObjGuid and 0x3C NextObj.
+        // See wow_offsets_reference.md: ObjectManager.CurObjGuid/NextObj
+        let guid = unsafe { read_offset::<u64>(self.current, 0x30) };
+        self.current = unsafe { read_offset::<u32>(self.current, 0x3C) };
+        Some(guid)
+    }
+}
+
+/// Read a null-terminated C string from game memory.
+///
+/// Returns `None` for null pointers and empty strings. Invalid UTF-8 is
+/// replaced rather than rejected so that names are always printable.
+pub unsafe fn read_c_string(addr: u32) -> Option<String> {
+    if addr == 0 {
+        return None;
+    }
+    // SAFETY: Caller guarantees addr points to a null-terminated string
+    // owned by the client (name caches live for the lifetime of the object).
+    let name = CStr::from_ptr(addr as *const c_char).to_string_lossy();
+    (!name.is_empty()).then(|| name.into_owned())
+}
+
+// =============================================================================
+// Typed Object Pointers
+// =============================================================================
+//
+// Raw object pointers are wrapped so that field reads are tied to the object
+// type they belong to. The only way to get a `UnitPtr`, `PlayerPtr` or
+// `GameObjectPtr` is a checked cast from an `ObjectPtr` based on `ObjectType`,
+// so reading a unit field from a game object no longer compiles.
+
+/// Untyped pointer to an object in the object manager.
+///
+/// Wrapping `NonZeroU32` keeps the same memory layout as `u32`
+/// (niche optimization) while ruling out null pointers.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectPtr(NonZeroU32);
+
+/// Pointer to an object's descriptor (update fields).
+///
+/// Fields are read through the typed accessors on the owning object.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorPtr(u32);
+
+/// Pointer to a unit (NPCs and players)
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnitPtr(ObjectPtr);
+
+/// Pointer to a player (also a unit)
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayerPtr(UnitPtr);
+
+/// Pointer to a game object (chests, herbs, mailboxes, ...)
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GameObjectPtr(ObjectPtr);
+
+/// Result of a type-checked cast of an `ObjectPtr`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WowObject {
+    Unit(UnitPtr),
+    Player(PlayerPtr),
+    GameObject(GameObjectPtr),
+    /// Any other object type (items, corpses, ...)
+    Other(ObjectPtr, ObjectType),
+}
+
+impl ObjectPtr {
+    /// Get the raw pointer value
+    #[inline]
+    pub fn raw(self) -> u32 {
+        self.0.get()
+    }
+
+    /// Get the object type.
+    ///
+    /// Reads at offset `0x14` from the object pointer.
+    #[inline]
+    pub unsafe fn object_type(self) -> ObjectType {
+        // SAFETY: self is a valid object pointer from get_object_pointer().
+        // Offset 0x14 is ObjType. See wow_offsets_reference.md: ObjectManager.ObjType
+        ObjectType::from(read_offset::<u32>(self.raw(), 0x14))
+    }
+
+    /// Get the descriptor pointer.
+    ///
+    /// Reads at offset `0x8` from the object pointer.
+    #[inline]
+    unsafe fn descriptor(self) -> DescriptorPtr {
+        // SAFETY: self is a valid object pointer.
+        // Offset 0x8 is DescriptorOffset. See wow_offsets_reference.md: ObjectManager.DescriptorOffset
+        DescriptorPtr(read_offset(self.raw(), 0x8))
+    }
+
+    /// Get the object's entry ID, e.g. the creature template of a unit;
+    /// 0 for players.
+    ///
+    /// Reads `OBJECT_FIELD_ENTRY` at descriptor offset `0xC`.
+    #[inline]
+    pub unsafe fn entry_id(self) -> u32 {
+        self.descriptor().field(0x0C)
+    }
+
+    /// Cast to a typed pointer based on the object type
+    pub unsafe fn cast(self) -> WowObject {
+        match self.object_type() {
+            ObjectType::Unit => WowObject::Unit(UnitPtr(self)),
+            ObjectType::Player => WowObject::Player(PlayerPtr(UnitPtr(self))),
+            ObjectType::GameObject => WowObject::GameObject(GameObjectPtr(self)),
+            other => WowObject::Other(self, other),
+        }
+    }
+
+    /// Cast to a unit pointer; players are units too
+    pub unsafe fn as_unit(self) -> Option<UnitPtr> {
+        match self.cast() {
+            WowObject::Unit(unit) => Some(unit),
+            WowObject::Player(player) => Some(player.unit()),
+            _ => None,
+        }
+    }
+
+    /// Cast to a player pointer
+    pub unsafe fn as_player(self) -> Option<PlayerPtr> {
+        match self.cast() {
+            WowObject::Player(player) => Some(player),
+            _ => None,
+        }
+    }
+
+    /// Cast to a game object pointer
+    pub unsafe fn as_gameobject(self) -> Option<GameObjectPtr> {
+        match self.cast() {
+            WowObject::GameObject(gameobject) => Some(gameobject),
+            _ => None,
+        }
+    }
+}
+
+impl DescriptorPtr {
+    /// Read a descriptor field at the given byte offset
+    #[inline]
+    unsafe fn field<T: Copy>(self, offset: u32) -> T {
+        // SAFETY: self is a descriptor pointer read from a valid object and
+        // offset is a 1.12.1 update field index multiplied by 4.
+        read_offset(self.0, offset)
+    }
+}
+
+impl WowObject {
+    /// Get the name of a unit or game object; `None` for other types
+    pub unsafe fn name(self) -> Option<String> {
+        match self {
+            Self::Unit(unit) => unit.name(),
+            Self::GameObject(gameobject) => gameobject.name(),
+            Self::Player(_) | Self::Other(..) => None,
+        }
+    }
+
+    /// Get the entry ID of a unit or game object; 0 for other types
+    pub unsafe fn entry_id(self) -> u32 {
+        match self {
+            Self::Unit(unit) => unit.object().entry_id(),
+            Self::GameObject(gameobject) => gameobject.entry_id(),
+            Self::Player(_) | Self::Other(..) => 0,
+        }
+    }
+}
+
+// =============================================================================
+// Unit Functions
+// =============================================================================
+
+impl UnitPtr {
+    /// Get the untyped pointer
+    #[inline]
+    pub fn object(self) -> ObjectPtr {
+        self.0
+    }
+
+    /// Get the raw pointer value
+    #[inline]
+    pub fn raw(self) -> u32 {
+        self.0.raw()
+    }
+
+    /// Get the raw position of the unit.
+    ///
+    /// Reads X/Y/Z coordinates from offsets `0x9B8`/`0x9BC`/`0x9C0`.
+    /// Note: WoW uses Y, X, Z order in memory. On transports these are not
+    /// reliable world coordinates; prefer `position`.
+    #[inline]
+    pub unsafe fn raw_position(self) -> C3Vector {
+        // SAFETY: self is a valid unit pointer.
+        // Offsets are from wow_offsets_reference.md: Unit.PosX/PosY/PosZ
+        C3Vector {
+            y: read_offset(self.raw(), 0x09B8),
+            x: read_offset(self.raw(), 0x09BC),
+            z: read_offset(self.raw(), 0x09C0),
+        }
+    }
+
+    /// Get the world position of the unit, accounting for transports.
+    ///
+    /// Reads the CMovement pointer at offset `0x118`; the position is at
+    /// `0x10` and the transport GUID at `0x38` of CMovement. When the unit is
+    /// on a transport, the position is local to it and is converted using
+    /// the transport's world position and facing. Falls back to
+    /// `raw_position` if the movement info or transport cannot be resolved.
+    pub unsafe fn position(self) -> C3Vector {
+        let Some(movement) = self.movement() else {
+            return self.raw_position();
+        };
+
+        // SAFETY: movement is the unit's CMovement pointer.
+        let local = C3Vector {
+            y: read_offset(movement, 0x10),
+            x: read_offset(movement, 0x14),
+            z: read_offset(movement, 0x18),
+        };
+
+        match Self::transport(movement) {
+            Ok(None) => local,
+            Ok(Some(transport)) => local.local_to_world(&transport.position(), transport.facing()),
+            Err(()) => self.raw_position(),
+        }
+    }
+
+    /// Get the world facing of the unit in radians, in `[0, 2π)`.
+    ///
+    /// Reads the facing at `0x1C` of CMovement, adding the transport's
+    /// facing when on a transport. Falls back to offset `0x9C4` if the
+    /// movement info or transport cannot be resolved.
+    pub unsafe fn facing(self) -> f32 {
+        // SAFETY: self is a valid unit pointer.
+        // Offset 0x9C4 is Unit.Rotation, next to the raw position fields.
+        let raw_facing = || read_offset::<f32>(self.raw(), 0x09C4);
+
+        let Some(movement) = self.movement() else {
+            return raw_facing();
+        };
+
+        // SAFETY: movement is the unit's CMovement pointer.
+        let local: f32 = read_offset(movement, 0x1C);
+        match Self::transport(movement) {
+            Ok(None) => local,
+            Ok(Some(transport)) => normalize_angle(local + transport.facing()),
+            Err(()) => raw_facing(),
+        }
+    }
+
+    /// Get the unit's CMovement pointer (offset `0x118`), if present
+    #[inline]
+    unsafe fn movement(self) -> Option<u32> {
+        // SAFETY: self is a valid unit pointer.
+        // Offset 0x118 is the CMovement pointer (see UnitXP Service Pack 3).
+        let movement: u32 = read_offset(self.raw(), 0x118);
+        (movement != 0).then_some(movement)
+    }
+
+    /// Resolve the transport referenced by a CMovement struct.
+    ///
+    /// Returns `Ok(None)` when not on a transport and `Err(())` when the
+    /// transport GUID at `0x38` is set but cannot be resolved.
+    unsafe fn transport(movement: u32) -> Result<Option<GameObjectPtr>, ()> {
+        let transport_guid: u64 = read_offset(movement, 0x38);
+        if transport_guid == 0 {
+            return Ok(None);
+        }
+        get_object_pointer(transport_guid)
+            .and_then(|p| p.as_gameobject())
+            .map(Some)
+            .ok_or(())
+    }
+
+    /// Get the health of the unit.
+    ///
+    /// Reads health from the unit's descriptor at offset `0x58`.
+    #[inline]
+    pub unsafe fn health(self) -> i32 {
+        // SAFETY: 0x58 is Health. See wow_offsets_reference.md: Descriptors.Health
+        self.0.descriptor().field(0x58)
+    }
+
+    /// Get the unit flags.
+    ///
+    /// Reads Flags from the unit's descriptor at offset `0xB8`.
+    #[inline]
+    pub unsafe fn flags(self) -> UnitFlags {
+        // SAFETY: 0xB8 is Flags. See wow_offsets_reference.md: Descriptors.Flags
+        UnitFlags(self.0.descriptor().field(0xB8))
+    }
+
+    /// Get the "summoned by" GUID.
+    ///
+    /// Reads the summoned-by GUID at descriptor offset `0x30`.
+    #[inline]
+    pub unsafe fn summoned_by_guid(self) -> u64 {
+        // SAFETY: 0x30 is SummonedByGuid. See wow_offsets_reference.md: Descriptors.SummonedByGuid
+        self.0.descriptor().field(0x30)
+    }
+
+    /// Read the unit's descriptor fields into a typed snapshot.
+    ///
+    /// Creature type and family come from the creature cache entry at `0xB30`
+    /// (type at `0x18`, family at `0x1C`) and are left empty when uncached.
+    pub unsafe fn read_descriptor(self) -> UnitDescriptor {
+        // SAFETY: self is a valid unit pointer; descriptor offsets are the
+        // 1.12.1 unit update fields listed in descriptors.rs.
+        let descriptor = self.0.descriptor();
+        let cache: u32 = read_offset(self.raw(), 0xB30);
+        let (creature_type, creature_family) = if cache == 0 {
+            (0, 0)
+        } else {
+            (read_offset(cache, 0x18), read_offset(cache, 0x1C))
+        };
+
+        UnitDescriptor {
+            summoned_by: descriptor.field(0x30),
+            target: descriptor.field(0x40),
+            health: descriptor.field(0x58),
+            max_health: descriptor.field(0x70),
+            level: descriptor.field(0x88),
+            faction_template: descriptor.field(0x8C),
+            flags: UnitFlags(descriptor.field(0xB8)),
+            dynamic_flags: DynamicFlags(descriptor.field(0x23C)),
+            npc_flags: NpcFlags(descriptor.field(0x24C)),
+            creature_type: CreatureType::from(creature_type),
+            creature_family,
+        }
+    }
+
+    /// Get the spell the unit is channeling (descriptor offset `0x240`), 0 if none.
+    #[inline]
+    pub unsafe fn channel_spell(self) -> u32 {
+        // SAFETY: 0x240 is ChannelSpell in the 1.12.1 unit update fields.
+        self.0.descriptor().field(0x240)
+    }
+
+    /// Get the spell the unit is casting (object offset `0xC8C`), 0 if none.
+    #[inline]
+    pub unsafe fn casting_spell(self) -> u32 {
+        // SAFETY: self is a valid unit pointer.
+        // Offset 0xC8C on the object is the spell currently being cast.
+        read_offset(self.raw(), 0xC8C)
+    }
+
+    /// Get the name of the unit from the creature cache.
+    ///
+    /// Reads the creature cache entry pointer at offset `0xB30`, then the name
+    /// pointer at offset `0x0` of the entry. Returns `None` for units whose cache
+    /// entry has not been received yet. Player names are not stored here.
+    pub unsafe fn name(self) -> Option<String> {
+        // SAFETY: self is a valid unit pointer.
+        // Offset 0xB30 is the CreatureCache entry, whose first field is the name.
+        let cache: u32 = read_offset(self.raw(), 0xB30);
+        if cache == 0 {
+            return None;
+        }
+        read_c_string(read_offset(cache, 0x0))
+    }
+}
+
+impl PlayerPtr {
+    /// Get the player as a unit
+    #[inline]
+    pub fn unit(self) -> UnitPtr {
+        self.0
+    }
+
+    /// Get the player flags.
+    ///
+    /// Reads PlayerFlags from the player's descriptor at offset `0x2F8`.
+    #[inline]
+    pub unsafe fn flags(self) -> u32 {
+        // SAFETY: 0x2F8 is PlayerFlags in the 1.12.1 player update fields.
+        self.0.object().descriptor().field(0x2F8)
+    }
+}
+
+// =============================================================================
+// Game Object Functions
+// =============================================================================
+
+impl GameObjectPtr {
+    /// Get the raw pointer value
+    #[inline]
+    pub fn raw(self) -> u32 {
+        self.0.raw()
+    }
+
+    /// Get the game object's entry ID.
+    ///
+    /// Reads at offset `0x294` from the object pointer.
+    #[inline]
+    pub unsafe fn entry_id(self) -> u32 {
+        // SAFETY: self is a valid GameObject pointer.
+        // Offset 0x294 contains the game object's entry ID.
+        read_offset(self.raw(), 0x294)
+    }
+
+    /// Read the game object's descriptor fields into a typed snapshot.
+    pub unsafe fn read_descriptor(self) -> GameObjectDescriptor {
+        // SAFETY: self is a valid GameObject pointer; descriptor offsets are
+        // the 1.12.1 game object update fields listed in descriptors.rs.
+        let descriptor = self.0.descriptor();
+        GameObjectDescriptor {
+            created_by: descriptor.field(0x18),
+            display_id: descriptor.field(0x20),
+            flags: GameObjectFlags(descriptor.field(0x24)),
+            state: GameObjectState::from(descriptor.field::<u32>(0x38)),
+            go_type: GameObjectType::from(descriptor.field::<u32>(0x54)),
+        }
+    }
+
+    /// Get the position of the game object.
+    ///
+    /// First reads a position structure pointer at offset `0x110`, then
+    /// reads the coordinates from that structure.
+    #[inline]
+    pub unsafe fn position(self) -> C3Vector {
+        // SAFETY: self is a valid GameObject pointer.
+        // Offset 0x110 points to a position structure.
+        // The position structure has Y/X/Z at offsets 0x24/0x28/0x2C.
+        let pos_ptr: u32 = read_offset(self.raw(), 0x110);
+        C3Vector {
+            y: read_offset(pos_ptr, 0x24),
+            x: read_offset(pos_ptr, 0x28),
+            z: read_offset(pos_ptr, 0x2C),
+        }
+    }
+
+    /// Get the facing of the game object in radians.
+    ///
+    /// Reads `GAMEOBJECT_FACING` at descriptor offset `0x48`.
+    #[inline]
+    pub unsafe fn facing(self) -> f32 {
+        // SAFETY: 0x48 is GAMEOBJECT_FACING in the 1.12.1 game object update fields.
+        self.0.descriptor().field(0x48)
+    }
+
+    /// Get the name of the game object from its info struct.
+    ///
+    /// Reads the gameobject info pointer at offset `0x214`, then the name
+    /// pointer at offset `0x8` of the info struct.
+    pub unsafe fn name(self) -> Option<String> {
+        // SAFETY: self is a valid GameObject pointer.
+        // Offset 0x214 is the GameObjectInfo entry, 0x8 within it is the name.
+        let info: u32 = read_offset(self.raw(), 0x214);
+        if info == 0 {
+            return None;
+        }
+        read_c_string(read_offset(info, 0x8))
+    }
+}
+
+// =============================================================================
+// World Queries
+// =============================================================================
+
+/// Check line of sight between two world positions.
+///
+/// Calls the game's `CWorld::Intersect` function at `0x672170`, tracing
+/// between points raised by `LOS_HEIGHT_OFFSET`. Returns `false` if terrain,
+/// a building or a doodad is in the way.
+pub unsafe fn has_line_of_sight(from: &C3Vector, to: &C3Vector) -> bool {
+    // SAFETY: WORLD_INTERSECT (0x672170) is CWorld::Intersect. All pointers
+    // passed are to locals that outlive the call.
+    let func: WorldIntersectFn = transmute(offsets::game::WORLD_INTERSECT);
+    let start = C3Vector {
+        z: from.z + LOS_HEIGHT_OFFSET,
+        ..*from
+    };
+    let end = C3Vector {
+        z: to.z + LOS_HEIGHT_OFFSET,
+        ..*to
+    };
+    let mut hit = C3Vector::default();
+    let mut distance: f32 = 1.0;
+    func(
+        &raw const start,
+        &raw const end,
+        0,
+        &raw mut hit,
+        &raw mut distance,
+        INTERSECT_FLAGS_LOS,
+    ) == 0
+}
+
+// =============================================================================
+// Object Walk
+// =============================================================================
+
+/// The client's visible objects, walked once, for `selection::select`.
+///
+/// `GetPtrForGuid` results are cached for the lifetime of the world, so
+/// the line of sight retries and summoner checks of a scan resolve each
+/// GUID once.
+pub struct GameWorld {
+    manager: Option<ObjectManager>,
+    /// Visible objects in list order, resolved when the world was built
+    objects: Vec<(u64, WowObject)>,
+    /// Resolved objects by GUID; `None` for GUIDs the client does not know
+    cache: RefCell<HashMap<u64, Option<WowObject>>>,
+    /// `GetPtrForGuid` calls made
+    lookups: Cell<u32>,
+    /// Lookups served from `cache`
+    cache_hits: Cell<u32>,
+}
+
+thread_local! {
+    /// The world of the current frame, shared by every script function
+    static FRAME_WORLD: RefCell<FrameCache<Rc<GameWorld>>> = const {
+        RefCell::new(FrameCache::new())
+    };
+}
+
+/// The visible objects of the current frame.
+///
+/// The object list is walked on the first call in a frame and shared by
+/// every later call in it, so a HUD polling each frame costs one walk. If
+/// the frame tick hook is not running, every call walks the list again.
+pub unsafe fn world() -> Rc<GameWorld> {
+    FRAME_WORLD.with_borrow_mut(|cache| {
+        cache.get_or_build(crate::hooks::frame_number(), || Rc::new(GameWorld::new()))
+    })
+}
+
+impl GameWorld {
+    /// Walk the object manager and resolve every visible object.
+    ///
+    /// The object pointers are only valid until the client next updates
+    /// its objects, so a `GameWorld` must not outlive the frame it was
+    /// built in; use `world()` rather than keeping one.
+    pub unsafe fn new() -> Self {
+        let world = Self {
+            manager: ObjectManager::get(),
+            objects: Vec::new(),
+            cache: RefCell::new(HashMap::new()),
+            lookups: Cell::new(0),
+            cache_hits: Cell::new(0),
+        };
+        let objects = world
+            .manager
+            .into_iter()
+            .flat_map(|manager| manager.guids())
+            .filter_map(|guid| world.resolve(guid).map(|object| (guid, object)))
+            .collect();
+        Self { objects, ..world }
+    }
+
+    /// GUID of the player, 0 if not in world
+    pub unsafe fn player_guid(&self) -> u64 {
+        self.manager.map_or(0, |manager| manager.player_guid())
+    }
+
+    /// Resolve a GUID, calling `GetPtrForGuid` only the first time
+    pub fn resolve(&self, guid: u64) -> Option<WowObject> {
+        if let Some(object) = self.cache.borrow().get(&guid) {
+            self.cache_hits.set(self.cache_hits.get() + 1);
+            return *object;
+        }
+        // SAFETY: GetPtrForGuid returns null for unknown GUIDs; the pointer
+        // is cast while it is valid, during the frame the world was built in
+        let object = unsafe { get_object_pointer(guid).map(|pointer| pointer.cast()) };
+        self.lookups.set(self.lookups.get() + 1);
+        self.cache.borrow_mut().insert(guid, object);
+        object
+    }
+
+    /// `GetPtrForGuid` calls made and lookups served from the cache
+    pub fn lookups(&self) -> (u32, u32) {
+        (self.lookups.get(), self.cache_hits.get())
+    }
+}
+
+impl World for GameWorld {
+    type Object = WowObject;
+
+    fn objects(&self) -> impl Iterator<Item = (u64, WowObject)> {
+        self.objects.iter().copied()
+    }
+
+    fn object_type(&self, object: WowObject) -> ObjectType {
+        match object {
+            WowObject::Unit(_) => ObjectType::Unit,
+            WowObject::Player(_) => ObjectType::Player,
+            WowObject::GameObject(_) => ObjectType::GameObject,
+            WowObject::Other(_, obj_type) => obj_type,
+        }
+    }
+
+    // SAFETY (all methods below): object was handed out by objects() during
+    // the current script call, so its pointer is still valid
+
+    fn position(&self, object: WowObject) -> C3Vector {
+        match object {
+            WowObject::Unit(unit) => unsafe { unit.position() },
+            WowObject::Player(player) => unsafe { player.unit().position() },
+            WowObject::GameObject(gameobject) => unsafe { gameobject.position() },
+            WowObject::Other(..) => C3Vector::default(),
+        }
+    }
+
+    fn name(&self, object: WowObject) -> Option<String> {
+        unsafe { object.name() }
+    }
+
+    fn summoned_by(&self, object: WowObject) -> u64 {
+        match object {
+            WowObject::Unit(unit) => unsafe { unit.summoned_by_guid() },
+            _ => 0,
+        }
+    }
+
+    fn type_of_guid(&self, guid: u64) -> Option<ObjectType> {
+        self.resolve(guid).map(|object| self.object_type(object))
+    }
+
+    fn unit_descriptor(&self, object: WowObject) -> UnitDescriptor {
+        match object {
+            WowObject::Unit(unit) => unsafe { unit.read_descriptor() },
+            _ => UnitDescriptor::default(),
+        }
+    }
+
+    fn entry_id(&self, object: WowObject) -> u32 {
+        unsafe { object.entry_id() }
+    }
+
+    fn gameobject_descriptor(&self, object: WowObject) -> GameObjectDescriptor {
+        match object {
+            WowObject::GameObject(gameobject) => unsafe { gameobject.read_descriptor() },
+            _ => GameObjectDescriptor::default(),
+        }
+    }
+
+    fn in_line_of_sight(&self, from: &C3Vector, object: WowObject) -> bool {
+        unsafe { has_line_of_sight(from, &self.position(object)) }
+    }
+}
+
+// =============================================================================
+// Interaction Functions
+// =============================================================================
+
+/// Set the current target by GUID.
+///
+/// Calls the game's `SetTarget` function at `0x493540`.
+#[inline]
+pub unsafe fn set_target(guid: u64) {
+    // SAFETY: SET_TARGET (0x493540) is the game's SetTarget function.
+    // It handles invalid GUIDs gracefully (clears target).
+    // See wow_offsets_reference.md: Functions.SetTarget
+    let func: SetTargetFn = transmute(offsets::game::SET_TARGET);
+    func(guid);
+}
+
+/// Interact with a unit (right-click).
+///
+/// Calls the game's `OnRightClickUnit` function at `0x60BEA0`.
+#[inline]
+pub unsafe fn interact_unit(unit: UnitPtr, autoloot: i32) {
+    // SAFETY: RIGHT_CLICK_UNIT (0x60BEA0) is OnRightClickUnit.
+    // unit is a type-checked unit pointer.
+    // See wow_offsets_reference.md: Functions.OnRightClickUnit
+    let func: RightClickFn = transmute(offsets::game::RIGHT_CLICK_UNIT);
+    func(unit.raw(), autoloot);
+}
+
+/// Interact with a game object (right-click).
+///
+/// Calls the game's `OnRightClickObject` function at `0x5F8660`.
+#[inline]
+pub unsafe fn interact_object(gameobject: GameObjectPtr, autoloot: i32) {
+    // SAFETY: RIGHT_CLICK_OBJECT (0x5F8660) is OnRightClickObject.
+    // gameobject is a type-checked GameObject pointer.
+    // See wow_offsets_reference.md: Functions.OnRightClickObject
+    let func: RightClickFn = transmute(offsets::game::RIGHT_CLICK_OBJECT);
+    func(gameobject.raw(), autoloot);
+}
@@ -0,0 +1,81 @@
+//! Interaction history for `InteractGetHistory`
+//!
+//! Wraps `interact_core::history::History` for the client's main thread.
+//! `InteractNearest` records each interaction; `LOOT_OPENED` and
+//! `UI_ERROR_MESSAGE` handlers settle its outcome, and a scheduled task
+//! settles it as nothing once the outcome window has passed. Every settled
+//! interaction is written to the trace log.
+//!
+//! Without the event hook every outcome is nothing; without the frame tick
+//! hook outcomes are settled by the next interaction or history query.
+
+use crate::{events, scheduler};
+use interact_core::config::Tier;
+use interact_core::events::{ClientEvent, EventKind};
+use interact_core::history::{History, Interaction, Outcome, OUTCOME_WINDOW_MS};
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    /// Interactions of the main thread, the only one that interacts
+    static HISTORY: RefCell<History> = RefCell::new(History::default());
+
+    /// Whether the event handlers have been subscribed
+    static SUBSCRIBED: Cell<bool> = const { Cell::new(false) };
+}
+
+fn log_settled(interaction: &Interaction) {
+    trace_log!("Interaction with {}", interaction);
+}
+
+/// Settle the pending interaction from an event
+fn observe(event: &ClientEvent) {
+    let now = scheduler::now();
+    HISTORY.with_borrow_mut(|history| {
+        if let Some(settled) = history.observe(event, now) {
+            log_settled(settled);
+        }
+    });
+}
+
+/// Settle the pending interaction as nothing if its window has passed
+fn expire() {
+    let now = scheduler::now();
+    HISTORY.with_borrow_mut(|history| {
+        if let Some(settled) = history.expire(now) {
+            log_settled(settled);
+        }
+    });
+}
+
+/// Subscribe the outcome handlers, once
+fn subscribe() {
+    if SUBSCRIBED.replace(true) {
+        return;
+    }
+    events::subscribe(EventKind::LootOpened, "history", observe);
+    events::subscribe(EventKind::UiErrorMessage, "history", observe);
+}
+
+/// Record an interaction and start waiting for its outcome
+pub fn record(guid: u64, entry: u32, category: Option<Tier>, distance: f32) {
+    subscribe();
+    let interaction = Interaction {
+        time_ms: scheduler::now(),
+        guid,
+        entry,
+        category,
+        distance,
+        outcome: Outcome::Pending,
+    };
+    if let Some(settled) = HISTORY.with_borrow_mut(|history| history.record(interaction)) {
+        log_settled(&settled);
+    }
+    // Fire just after the window so the interaction is past it
+    scheduler::after("history", OUTCOME_WINDOW_MS + 1, expire);
+}
+
+/// Up to `count` interactions, newest first
+pub fn recent(count: usize) -> Vec<Interaction> {
+    expire();
+    HISTORY.with_borrow(|history| history.recent(count).cloned().collect())
+}
@@ -0,0 +1,288 @@
+//! Function hooks for WoW 1.12.1
+//!
+//! Uses the `retour` crate to hook game functions for:
+//! - Bootstrap initialization
+//! - Lua function registration
+//! - Counting frames, for per-frame caches, and running scheduled tasks
+//! - Passing client UI events to Rust handlers
+
+use crate::errors::HookError;
+use crate::events::{self, VarArgs};
+use crate::{lua, offsets, scheduler, scripts};
+use once_cell::sync::Lazy;
+use retour::static_detour;
+use std::ffi::c_char;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use windows::core::PCSTR;
+use windows::Win32::Storage::FileSystem::{
+    GetFileVersionInfoA, GetFileVersionInfoSizeA, VerQueryValueA, VS_FIXEDFILEINFO,
+};
+use windows::Win32::System::LibraryLoader::GetModuleFileNameA;
+
+// =============================================================================
+// Version Information
+// =============================================================================
+
+/// Version string extracted from Cargo.toml at compile time.
+/// Format: "MAJOR.MINOR.PATCH" (e.g., "1.2.0")
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Client build read from the version resource of the running executable
+/// (5875 for 1.12.1), or `None` if it could not be read
+pub fn client_build() -> Option<u32> {
+    static BUILD: Lazy<Option<u32>> = Lazy::new(|| unsafe { read_client_build() });
+    *BUILD
+}
+
+/// Read the build number (lowest word of the file version) of the executable
+unsafe fn read_client_build() -> Option<u32> {
+    let mut path = [0u8; 260];
+    let len = GetModuleFileNameA(None, &mut path[..259]) as usize;
+    if len == 0 {
+        return None;
+    }
+    let path = PCSTR::from_raw(path.as_ptr());
+
+    let size = GetFileVersionInfoSizeA(path, None);
+    if size == 0 {
+        return None;
+    }
+    let mut data = vec![0u8; size as usize];
+    GetFileVersionInfoA(path, 0, size, data.as_mut_ptr().cast()).ok()?;
+
+    let mut info: *mut std::ffi::c_void = std::ptr::null_mut();
+    let mut info_len = 0u32;
+    let found = VerQueryValueA(
+        data.as_ptr().cast(),
+        PCSTR::from_raw(c"\\".as_ptr().cast()),
+        &raw mut info,
+        &raw mut info_len,
+    );
+    if !found.as_bool() || info.is_null() {
+        return None;
+    }
+    let info = &*info.cast::<VS_FIXEDFILEINFO>();
+    Some(info.dwFileVersionLS & 0xFFFF)
+}
+
+// =============================================================================
+// Function Type Definitions
+// =============================================================================
+
+/// void __fastcall SysMsgInitialize()
+type SysMsgInitializeFn = extern "fastcall" fn();
+
+/// void __stdcall LoadScriptFunctions()
+type LoadScriptFunctionsFn = extern "stdcall" fn();
+
+/// void __fastcall ISceneEnd(void* device)
+type SceneEndFn = extern "fastcall" fn(u32);
+
+/// void __cdecl FrameScript_SignalEvent(uint32_t event, const char* format, ...)
+/// Declared with a fixed block of variadic words, see `events::VARARG_WORDS`
+type SignalEventFn = extern "C" fn(u32, *const c_char, VarArgs);
+
+// =============================================================================
+// Static Detours
+// =============================================================================
+
+static_detour! {
+    static SysMsgInitHook: extern "fastcall" fn();
+    static LoadScriptFunctionsHook: extern "stdcall" fn();
+    static SceneEndHook: extern "fastcall" fn(u32);
+    static SignalEventHook: extern "C" fn(u32, *const c_char, VarArgs);
+}
+
+// =============================================================================
+// Initialization State
+// =============================================================================
+
+static HOOKS_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Frames ended since the frame tick hook was installed; 0 before the first
+static FRAME: AtomicU32 = AtomicU32::new(0);
+
+/// Number of the current frame, or `None` if the frame tick hook is not
+/// running and frames cannot be told apart
+pub fn frame_number() -> Option<u32> {
+    if !SceneEndHook.is_enabled() {
+        return None;
+    }
+    match FRAME.load(Ordering::Relaxed) {
+        0 => None,
+        frame => Some(frame),
+    }
+}
+
+/// Installation state of the hooks, for diagnostics
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)] // One flag per hook
+pub struct HookStatus {
+    /// `SysMsgInitialize` has run and initialized the secondary hooks
+    pub initialized: bool,
+    pub bootstrap_enabled: bool,
+    pub script_functions_enabled: bool,
+    pub frame_tick_enabled: bool,
+    pub events_enabled: bool,
+}
+
+/// Current state of the hooks
+pub fn status() -> HookStatus {
+    HookStatus {
+        initialized: HOOKS_INITIALIZED.load(Ordering::SeqCst),
+        bootstrap_enabled: SysMsgInitHook.is_enabled(),
+        script_functions_enabled: LoadScriptFunctionsHook.is_enabled(),
+        frame_tick_enabled: SceneEndHook.is_enabled(),
+        events_enabled: SignalEventHook.is_enabled(),
+    }
+}
+
+// =============================================================================
+// Hook Implementations
+// =============================================================================
+
+/// SysMsgInitialize hook - bootstraps all other hooks
+fn sys_msg_init_detour() {
+    // Call original
+    SysMsgInitHook.call();
+
+    // Initialize hooks only once
+    if HOOKS_INITIALIZED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    // Initialize logging (safe to do file I/O now, we're past DllMain)
+    crate::logging::init();
+
+    debug_log!("=== interact-rs v{} ===", VERSION);
+    debug_log!("SysMsgInitialize called - initializing hooks");
+
+    match client_build() {
+        Some(build) if build == offsets::CLIENT_BUILD => debug_log!("Client build {}", build),
+        Some(build) => debug_log!(
+            "WARNING: client build {} does not match offset table {}",
+            build,
+            offsets::TABLE_NAME
+        ),
+        None => debug_log!("Client build could not be read"),
+    }
+
+    // Load settings (errors are logged and the defaults kept)
+    let _ = scripts::load_config();
+
+    // Initialize all other hooks
+    unsafe {
+        match init_all_hooks() {
+            Ok(()) => debug_log!("All hooks initialized successfully"),
+            Err(e) => {
+                crate::diagnostics::record_error(format!("Failed to initialize hooks: {e}"));
+            }
+        }
+    }
+}
+
+/// LoadScriptFunctions hook - register our Lua functions
+fn load_script_functions_detour() {
+    // Call original first
+    LoadScriptFunctionsHook.call();
+
+    debug_log!("LoadScriptFunctions called - registering Lua functions");
+
+    // Initialize Lua API
+    unsafe {
+        lua::init();
+
+        // Register our custom Lua functions
+        scripts::register_functions();
+    }
+
+    debug_log!("Lua functions registered");
+}
+
+/// ISceneEnd hook - starts the next frame once this one has ended and runs
+/// the scheduled tasks that are due
+fn scene_end_detour(device: u32) {
+    SceneEndHook.call(device);
+
+    // Skip 0, which means no frame has ended yet
+    if FRAME.fetch_add(1, Ordering::Relaxed) == u32::MAX {
+        FRAME.store(1, Ordering::Relaxed);
+    }
+
+    scheduler::tick();
+}
+
+/// FrameScript_SignalEvent hook - passes events to Rust handlers once the
+/// client and AddOns have seen them
+fn signal_event_detour(event: u32, format: *const c_char, args: VarArgs) {
+    SignalEventHook.call(event, format, args);
+
+    unsafe { events::signal(event, format, &args) };
+}
+
+// =============================================================================
+// Hook Initialization
+// =============================================================================
+
+/// Initialize all secondary hooks (called from SysMsgInit hook)
+unsafe fn init_all_hooks() -> Result<(), HookError> {
+    // Hook LoadScriptFunctions
+    let load_script_functions: LoadScriptFunctionsFn =
+        std::mem::transmute(offsets::bootstrap::LOAD_SCRIPT_FUNCTIONS);
+
+    LoadScriptFunctionsHook
+        .initialize(load_script_functions, load_script_functions_detour)
+        .map_err(|e| HookError::InitFailed {
+            addr: offsets::bootstrap::LOAD_SCRIPT_FUNCTIONS,
+            message: e.to_string(),
+        })?
+        .enable()
+        .map_err(|e| HookError::EnableFailed(e.to_string()))?;
+
+    // Hook ISceneEnd and FrameScript_SignalEvent last: without them only
+    // per-frame caching, scheduled tasks and event handlers are lost
+    let scene_end: SceneEndFn = std::mem::transmute(offsets::game::SCENE_END);
+
+    SceneEndHook
+        .initialize(scene_end, scene_end_detour)
+        .map_err(|e| HookError::InitFailed {
+            addr: offsets::game::SCENE_END,
+            message: e.to_string(),
+        })?
+        .enable()
+        .map_err(|e| HookError::EnableFailed(e.to_string()))?;
+
+    let signal_event: SignalEventFn = std::mem::transmute(offsets::events::SIGNAL_EVENT);
+
+    SignalEventHook
+        .initialize(signal_event, signal_event_detour)
+        .map_err(|e| HookError::InitFailed {
+            addr: offsets::events::SIGNAL_EVENT,
+            message: e.to_string(),
+        })?
+        .enable()
+        .map_err(|e| HookError::EnableFailed(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Main entry point - install bootstrap hook
+///
+/// # Safety
+/// Must be called from DllMain during DLL_PROCESS_ATTACH
+pub unsafe fn load() -> Result<(), HookError> {
+    // Hook SysMsgInitialize as bootstrap
+    let sys_msg_init: SysMsgInitializeFn =
+        std::mem::transmute(offsets::bootstrap::SYS_MSG_INITIALIZE);
+
+    SysMsgInitHook
+        .initialize(sys_msg_init, sys_msg_init_detour)
+        .map_err(|e| HookError::InitFailed {
+            addr: offsets::bootstrap::SYS_MSG_INITIALIZE,
+            message: e.to_string(),
+        })?
+        .enable()
+        .map_err(|e| HookError::EnableFailed(e.to_string()))?;
+
+    Ok(())
+}
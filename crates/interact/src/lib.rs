@@ -2,7 +2,12 @@
 //!
 //! A Rust port of the Interact DLL that provides an "interact with nearest object"
 //! keybind functionality for the WoW 1.12.1 Vanilla client.
+//!
+//! This crate holds everything that needs the client: `DllMain`, hooks, the
+//! Lua API and raw memory access. The rest lives in `interact-core`. The DLL
+//! only exists for 32-bit Windows, so on any other target the crate is empty.
 
+#![cfg(all(windows, target_arch = "x86"))]
 // =============================================================================
 // Lints
 // =============================================================================
@@ -29,15 +34,20 @@
 
 #[macro_use]
 mod logging;
+mod diagnostics;
 mod errors;
+mod events;
 mod game;
+mod history;
 mod hooks;
 mod lua;
 mod offsets;
+mod scheduler;
 mod scripts;
 
-pub use errors::{HookError, InteractError, LuaError};
+pub use errors::{ConfigError, HookError, InteractError, LuaError, SnapshotError};
 
+use interact_core::{commands, config, descriptors};
 use std::ffi::c_void;
 use windows::Win32::Foundation::{BOOL, FALSE, HINSTANCE, TRUE};
 use windows::Win32::System::SystemServices::{DLL_PROCESS_ATTACH, DLL_PROCESS_DETACH};
@@ -10,6 +10,7 @@ use windows::Win32::Storage::FileSystem::{
     CreateDirectoryA, CreateFileA, DeleteFileA, FlushFileBuffers, MoveFileA, WriteFile,
     CREATE_ALWAYS, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_READ,
 };
+use windows::Win32::System::Performance::{QueryPerformanceCounter, QueryPerformanceFrequency};
 use windows::Win32::System::SystemInformation::GetLocalTime;
 
 // =============================================================================
@@ -80,6 +81,8 @@ pub fn init() {
         match handle {
             Ok(h) if h != INVALID_HANDLE_VALUE => {
                 LOG_HANDLE.store(handle_to_atomic(h), Ordering::SeqCst);
+                // interact-core logs through this file too
+                interact_core::log::set_writer(log_debug);
 
                 // Write initialization message
                 let init_msg = b"[INIT] interact-rs logging initialized\r\n";
@@ -112,6 +115,13 @@ pub fn log_debug(message: &str) {
     }
 }
 
+/// Check if trace messages are enabled. The flag is set in interact-core,
+/// by the config and the script functions.
+#[inline]
+pub fn trace_enabled() -> bool {
+    interact_core::log::trace_enabled()
+}
+
 /// Shutdown logging and close the file handle
 pub fn shutdown() {
     let handle_val = LOG_HANDLE.swap(0, Ordering::SeqCst);
@@ -122,6 +132,51 @@ pub fn shutdown() {
     }
 }
 
+/// Local time for file names, e.g. `20260101_120000`
+pub fn file_timestamp() -> String {
+    let st = unsafe { GetLocalTime() };
+    format!(
+        "{:04}{:02}{:02}_{:02}{:02}{:02}",
+        st.wYear, st.wMonth, st.wDay, st.wHour, st.wMinute, st.wSecond
+    )
+}
+
+// =============================================================================
+// Timing
+// =============================================================================
+
+/// Measures elapsed time with `QueryPerformanceCounter`, for trace messages
+pub struct Stopwatch {
+    start: i64,
+}
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Self {
+            start: performance_counter(),
+        }
+    }
+
+    /// Milliseconds since `start`
+    pub fn elapsed_ms(&self) -> f64 {
+        let mut frequency: i64 = 0;
+        // SAFETY: frequency is a valid out pointer; the call cannot fail on
+        // Windows XP and later
+        let _ = unsafe { QueryPerformanceFrequency(&raw mut frequency) };
+        if frequency <= 0 {
+            return 0.0;
+        }
+        (performance_counter() - self.start) as f64 * 1000.0 / frequency as f64
+    }
+}
+
+fn performance_counter() -> i64 {
+    let mut counter: i64 = 0;
+    // SAFETY: counter is a valid out pointer
+    let _ = unsafe { QueryPerformanceCounter(&raw mut counter) };
+    counter
+}
+
 // =============================================================================
 // Internal Helpers
 // =============================================================================
@@ -167,3 +222,13 @@ macro_rules! debug_log {
         $crate::logging::log_debug(&format!($($arg)*))
     };
 }
+
+/// Verbose logging, only formatted and written when trace is enabled
+#[macro_export]
+macro_rules! trace_log {
+    ($($arg:tt)*) => {
+        if $crate::logging::trace_enabled() {
+            $crate::logging::log_debug(&format!("[TRACE] {}", format_args!($($arg)*)))
+        }
+    };
+}
@@ -7,6 +7,12 @@
 //! we use the original Interact C implementation values. UnitXP alternatives
 //! are noted in comments for future reference.
 
+/// Client build the offsets in this file were taken from
+pub const CLIENT_BUILD: u32 = 5875;
+
+/// Name of this offset table, reported by `InteractGetVersion`
+pub const TABLE_NAME: &str = "1.12.1.5875";
+
 /// Bootstrap / Initialization Hooks
 pub mod bootstrap {
     /// void __fastcall SysMsgInitialize()
@@ -36,6 +42,28 @@ pub mod game {
 
     /// Pointer to visible objects manager
     pub const VISIBLE_OBJECTS: usize = 0x00B41414;
+
+    /// bool __fastcall CWorld::Intersect(const C3Vector* start, const C3Vector* end,
+    ///     int unused, C3Vector* hit, float* distance, uint32_t flags)
+    /// Note: Same address as UnitXP's line of sight check
+    pub const WORLD_INTERSECT: usize = 0x00672170;
+
+    /// void __fastcall ISceneEnd(void* device)
+    /// Ends every rendered frame, on the main thread
+    pub const SCENE_END: usize = 0x005A17A0;
+}
+
+/// Client UI Events
+pub mod events {
+    /// void __cdecl FrameScript_SignalEvent(uint32_t event, const char* format, ...)
+    /// Note: not yet verified against a running client
+    pub const SIGNAL_EVENT: usize = 0x00703F50;
+
+    /// Pointer to the event name table, `const char*` indexed by event ID
+    pub const EVENT_NAMES: usize = 0x00CEEF68;
+
+    /// uint32_t number of entries in the event name table
+    pub const EVENT_COUNT: usize = 0x00CEEF6C;
 }
 
 /// Lua C API Functions (__fastcall unless noted)
@@ -52,6 +80,9 @@ pub mod lua_api {
     pub const PUSHSTRING: usize = 0x006F3890;
     pub const PUSHNIL: usize = 0x006F37F0;
     pub const PUSHBOOLEAN: usize = 0x006F39F0;
+    pub const NEWTABLE: usize = 0x006F3C90;
+    /// Note: pops the key and value, like Lua 5.0 `lua_settable`
+    pub const SETTABLE: usize = 0x006F3E20;
     /// Note: __cdecl, takes message parameter directly
     pub const ERROR: usize = 0x006F4940;
 }
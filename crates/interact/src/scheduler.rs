@@ -0,0 +1,79 @@
+//! Tasks run from the frame tick hook
+//!
+//! Wraps `interact_core::scheduler::Scheduler` for the client's main thread.
+//! Tasks are scheduled with `next_frame`, `after` or `every` and run at the
+//! end of a frame by `tick`, which the `ISceneEnd` hook calls. Without that
+//! hook nothing scheduled here ever runs.
+
+use interact_core::scheduler::{Scheduler, TaskId};
+use std::cell::RefCell;
+use windows::Win32::System::SystemInformation::GetTickCount64;
+
+thread_local! {
+    /// Tasks of the main thread, the only one that schedules or ticks
+    static SCHEDULER: RefCell<Scheduler> = RefCell::new(Scheduler::new());
+}
+
+/// Milliseconds since the system started
+pub fn now() -> u64 {
+    unsafe { GetTickCount64() }
+}
+
+// =============================================================================
+// Scheduling
+// =============================================================================
+
+/// Run `task` once, at the end of this frame
+#[allow(dead_code)] // Scheduling API for delayed behaviours
+pub fn next_frame(name: &'static str, task: impl FnMut() + 'static) -> TaskId {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.next_tick(name, task))
+}
+
+/// Run `task` once, `delay_ms` milliseconds from now
+pub fn after(name: &'static str, delay_ms: u64, task: impl FnMut() + 'static) -> TaskId {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.after(name, now(), delay_ms, task))
+}
+
+/// Run `task` every `interval_ms` milliseconds, or every frame if 0
+#[allow(dead_code)] // Scheduling API for delayed behaviours
+pub fn every(name: &'static str, interval_ms: u64, task: impl FnMut() + 'static) -> TaskId {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.every(name, now(), interval_ms, task))
+}
+
+/// Stop a task; `false` if it was not scheduled
+#[allow(dead_code)] // Scheduling API for delayed behaviours
+pub fn cancel(id: TaskId) -> bool {
+    SCHEDULER.with_borrow_mut(|scheduler| scheduler.cancel(id))
+}
+
+/// Number of scheduled tasks, for diagnostics
+pub fn pending() -> usize {
+    SCHEDULER.with_borrow(Scheduler::len)
+}
+
+// =============================================================================
+// Tick
+// =============================================================================
+
+/// Run the tasks that are due. Called once per frame from the frame tick
+/// hook.
+///
+/// Tasks run outside the scheduler's borrow so they can schedule or cancel
+/// tasks themselves. A task that panics is dropped and the panic recorded
+/// as the last error; the remaining tasks still run.
+pub fn tick() {
+    let now = now();
+    let due = SCHEDULER.with_borrow_mut(|scheduler| scheduler.take_due(now));
+    for mut task in due {
+        match task.run() {
+            Ok(()) => SCHEDULER.with_borrow_mut(|scheduler| scheduler.requeue(task, now)),
+            Err(message) => {
+                crate::diagnostics::record_error(format!(
+                    "Scheduled task '{}' panicked and was dropped: {message}",
+                    task.name()
+                ));
+                SCHEDULER.with_borrow_mut(|scheduler| scheduler.discard(&task));
+            }
+        }
+    }
+}
@@ -0,0 +1,808 @@
+//! Lua script functions for interact
+//!
+//! Implements the Lua API:
+//! - InteractNearest(autoloot) - Interact with the nearest valid object
+//! - InteractSetNameFilters(include, exclude) - Filter candidates by name
+//! - InteractSetRange(distance, maxHeight, unitRadius, objectRadius) - Range model
+//! - InteractGetRange() - Current range settings
+//! - InteractSetObjectRadius(entryId, radius) - Radius of a game object entry
+//! - InteractSetFacing(cone, weight) - Prefer objects in front of the player
+//! - InteractSetLineOfSight(enabled, maxChecks) - Skip objects behind walls
+//! - InteractSetPriority(order) - Order in which candidate tiers are selected
+//! - InteractSetBlacklist(ids) - Game objects that are never selected
+//! - InteractSetTrace(enabled) - Toggle verbose trace logging
+//! - InteractReloadConfig() - Re-read interact.toml
+//! - InteractCommand(msg) - Run a /interact slash command
+//! - InteractGetVersion() - DLL version and capabilities
+//! - InteractDiagnostics() - Self-check report
+//! - InteractCaptureSnapshot() - Save the visible objects for a bug report
+//! - InteractGetHistory(n) - Recent interactions and their outcomes
+
+use crate::commands::{self, Command};
+use crate::config::{self, Config};
+use crate::diagnostics;
+use crate::errors::{ConfigError, SnapshotError};
+use crate::game::{self, WorldState, WowObject};
+use crate::history;
+use crate::lua::{self, LuaState};
+use interact_core::script;
+use interact_core::selection::{self, Candidate, PlayerView, Rules, Verdict, World};
+use interact_core::settings::{
+    self, name_filter, FACING, LINE_OF_SIGHT, OBJECT_RADII, PRIORITY, RANGE,
+};
+use interact_core::snapshot::Snapshot;
+use std::ffi::{c_int, c_void};
+use std::path::{Path, PathBuf};
+use std::sync::PoisonError;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Maximum number of objects listed by `/interact dump`
+const DUMP_LIMIT: usize = 15;
+
+/// Directory snapshots are written to, next to the debug log
+const SNAPSHOT_DIR: &str = "Logs";
+
+/// Candidate holding a type-checked game object pointer
+type GameCandidate = Candidate<Option<WowObject>>;
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// Read `interact.toml` and apply it, logging any errors.
+///
+/// On error the current settings are kept.
+pub fn load_config() -> Result<(), ConfigError> {
+    let result = Config::load(Path::new(config::CONFIG_FILE));
+    match &result {
+        Ok(config) => {
+            settings::apply(config);
+            debug_log!("Config loaded from {}", config::CONFIG_FILE);
+        }
+        Err(e) => diagnostics::record_error(format!("{e} - keeping current settings")),
+    }
+    diagnostics::record_config_status(result.as_ref().map(|_| ()).map_err(ToString::to_string));
+    result.map(|_| ())
+}
+
+// =============================================================================
+// Script_InteractNearest
+// =============================================================================
+//
+// Lua: InteractNearest(autoloot)
+//
+// Finds and interacts with the nearest valid object within 5 yards.
+// Returns no values to Lua (matching original C behavior).
+//
+// Parameters:
+//   autoloot - 0 for normal interact, non-zero for auto-loot (number)
+//
+// Priority order:
+//   1. Lootable corpses (dead units with loot)
+//   2. Game objects (chests, herbs, mining nodes, etc.)
+//   3. Skinnable corpses (dead units without loot but skinnable)
+//   4. Alive units (NPCs)
+//   5. Service objects (mailboxes, auction boards, meeting stones)
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractNearest(_lua_state: LuaState) -> c_int {
+    // Check the player can interact at all (not loading, dead, on a taxi or casting)
+    let state = game::get_world_state();
+    if !state.can_interact() {
+        debug_log!("InteractNearest skipped: {:?}", state);
+        return 0;
+    }
+
+    let lua = lua::api();
+    script::nearest(lua, lua.get_state(), |autoloot| {
+        interact_nearest(state, autoloot)
+    })
+}
+
+/// Interact with the best candidate, returning the Lua result count
+unsafe fn interact_nearest(state: WorldState, autoloot: i32) -> c_int {
+    let Some(candidate) = find_best_candidate(state) else {
+        return 0;
+    };
+    let Some(object) = candidate.pointer else {
+        return 0;
+    };
+
+    let name = object.name();
+    debug_log!(
+        "Interacting with {} (guid {:#018x}, {:?}, {:.2} yd)",
+        name.as_deref().unwrap_or("<unknown>"),
+        candidate.guid,
+        candidate.obj_type,
+        candidate.distance
+    );
+
+    // Perform the interaction
+    match object {
+        WowObject::Unit(unit) => {
+            game::set_target(candidate.guid);
+            game::interact_unit(unit, autoloot);
+        }
+        WowObject::GameObject(gameobject) => {
+            game::interact_object(gameobject, autoloot);
+        }
+        _ => return 0,
+    }
+    history::record(
+        candidate.guid,
+        object.entry_id(),
+        candidate.tier,
+        candidate.distance,
+    );
+
+    1 // Return value count (C version returns 1 on success, 0 on failure)
+}
+
+/// Find the best interaction candidate based on priority rules, retrying
+/// without candidates that fail the line of sight check
+unsafe fn find_best_candidate(state: WorldState) -> Option<GameCandidate> {
+    let stopwatch = crate::logging::Stopwatch::start();
+    let world = game::world();
+    let player_guid = world.player_guid();
+    let player = game::get_object_pointer(player_guid)?.as_unit()?;
+
+    let radii = OBJECT_RADII.read().unwrap_or_else(PoisonError::into_inner);
+    let filter = name_filter();
+    let priority = PRIORITY.read().unwrap_or_else(PoisonError::into_inner);
+    let rules = Rules {
+        range: *RANGE.read().unwrap_or_else(PoisonError::into_inner),
+        radii: &radii,
+        facing: *FACING.read().unwrap_or_else(PoisonError::into_inner),
+        line_of_sight: *LINE_OF_SIGHT.read().unwrap_or_else(PoisonError::into_inner),
+        filter: &filter,
+        priority: &priority,
+    };
+
+    // Facing is only read when a cone or angle weight is configured
+    let view = PlayerView {
+        guid: player_guid,
+        position: player.position(),
+        facing: if rules.facing.is_enabled() {
+            player.facing()
+        } else {
+            0.0
+        },
+    };
+
+    let mut los_failures = 0;
+    let candidate = selection::select(&*world, &view, state, &rules, |guid, object, verdict| {
+        if let Verdict::NoLineOfSight { distance } = verdict {
+            los_failures += 1;
+            trace_log!(
+                "Skipping {} (guid {:#018x}, {:.2} yd): no line of sight",
+                object.name().as_deref().unwrap_or("<unknown>"),
+                guid,
+                distance
+            );
+        }
+    });
+    if candidate.is_none() && los_failures > 0 {
+        trace_log!(
+            "No candidate in line of sight after {} checks",
+            los_failures
+        );
+    }
+    let (lookups, cached) = world.lookups();
+    trace_log!(
+        "Scan took {:.3} ms: {} objects, {} GetPtrForGuid calls, {} served from cache",
+        stopwatch.elapsed_ms(),
+        world.objects().count(),
+        lookups,
+        cached
+    );
+    candidate
+}
+
+// =============================================================================
+// Script_InteractSetNameFilters
+// =============================================================================
+//
+// Lua: InteractSetNameFilters(include, exclude)
+//
+// Replaces the name filters applied to candidates. Both arguments are
+// comma-separated, case-insensitive patterns supporting `*` and `?`.
+// Pass nil or "" to clear a list. Returns no values.
+//
+// Example: InteractSetNameFilters("*Vein,*Deposit", "Dusty*")
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetNameFilters(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_name_filters(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetRange
+// =============================================================================
+//
+// Lua: InteractSetRange(distance, maxHeight, unitRadius, objectRadius)
+//
+// Configures how far away objects may be. Returns no values.
+//
+// Parameters:
+//   distance     - Maximum distance in yards (number, default 5)
+//   maxHeight    - Optional maximum height difference in yards. When set,
+//                  distance is measured horizontally; nil or 0 measures
+//                  full 3D distance (number)
+//   unitRadius   - Optional yards subtracted from the distance to units (number)
+//   objectRadius - Optional yards subtracted from the distance to game
+//                  objects (number)
+//
+// Example: InteractSetRange(5, 3)
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetRange(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_range(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractGetRange
+// =============================================================================
+//
+// Lua: InteractGetRange()
+//
+// Reports the current range settings, so one of them can be changed without
+// resetting the others.
+//
+// Returns:
+//   distance, maxHeight, unitRadius, objectRadius - As passed to
+//   InteractSetRange; maxHeight is 0 for full 3D distance (numbers)
+//
+// Example: local distance, maxHeight, unitRadius, objectRadius = InteractGetRange()
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractGetRange(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::get_range(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetObjectRadius
+// =============================================================================
+//
+// Lua: InteractSetObjectRadius(entryId, radius)
+//
+// Sets the bounding radius of a game object entry, subtracted from its
+// distance in place of the objectRadius given to InteractSetRange. Returns
+// no values.
+//
+// Parameters:
+//   entryId - Game object entry ID, or nil to clear all entries (number)
+//   radius  - Radius in yards; nil or 0 removes the entry (number)
+//
+// Example: InteractSetObjectRadius(32349, 2)
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetObjectRadius(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_object_radius(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetFacing
+// =============================================================================
+//
+// Lua: InteractSetFacing(cone, weight)
+//
+// Configures facing-aware selection. Returns no values.
+//
+// Parameters:
+//   cone   - Full width in degrees of the cone in front of the player that
+//            candidates must be in; 0 or 360 disables the cone (number)
+//   weight - Yards added to the distance of an object directly behind the
+//            player, scaled linearly with the angle; 0 disables (number)
+//
+// Example: InteractSetFacing(120, 2)
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetFacing(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_facing(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetLineOfSight
+// =============================================================================
+//
+// Lua: InteractSetLineOfSight(enabled, maxChecks)
+//
+// Enables or disables line of sight checks. Returns no values.
+//
+// Parameters:
+//   enabled   - 0 to disable, non-zero to enable (number)
+//   maxChecks - Optional number of candidates to trace before giving up,
+//               at least 1 (number, default 3)
+//
+// Example: InteractSetLineOfSight(1, 3)
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetLineOfSight(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_line_of_sight(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetPriority
+// =============================================================================
+//
+// Lua: InteractSetPriority(order)
+//
+// Sets the order in which candidate tiers are selected: the nearest object
+// of the first tier that has one is used.
+//
+// Parameters:
+//   order - Comma-separated tier names: lootable, gameobject, skinnable,
+//           alive, service. Tiers left out are never selected. nil restores
+//           the default order (string)
+//
+// Returns:
+//   true on success, or nil and an error message
+//
+// Example: InteractSetPriority("gameobject,lootable,skinnable")
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetPriority(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_priority(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetBlacklist
+// =============================================================================
+//
+// Lua: InteractSetBlacklist(ids)
+//
+// Replaces the list of game objects that are never selected.
+//
+// Parameters:
+//   ids - Comma-separated game object entry IDs; "" selects everything and
+//         nil restores the built-in list (string)
+//
+// Returns:
+//   true on success, or nil and an error message
+//
+// Example: InteractSetBlacklist("179830,179831")
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetBlacklist(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_blacklist(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractSetTrace
+// =============================================================================
+//
+// Lua: InteractSetTrace(enabled)
+//
+// Enables or disables verbose trace messages in Logs\interact_debug.log,
+// such as why candidates were skipped. Returns no values.
+//
+// Parameters:
+//   enabled - 0 to disable, non-zero to enable (number)
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractSetTrace(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::set_trace(lua, lua.get_state())
+}
+
+// =============================================================================
+// Script_InteractReloadConfig
+// =============================================================================
+//
+// Lua: InteractReloadConfig()
+//
+// Re-reads interact.toml from the WoW directory and replaces all settings,
+// including any made with the InteractSet* functions. A missing file resets
+// everything to the defaults. On error the current settings are kept.
+//
+// Returns:
+//   true on success, or nil and an error message
+//
+// Example: local ok, err = InteractReloadConfig()
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractReloadConfig(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    let l = lua.get_state();
+
+    script::push_result(lua, l, load_config())
+}
+
+// =============================================================================
+// Script_InteractCommand
+// =============================================================================
+//
+// Lua: InteractCommand(msg)
+//
+// Runs a /interact slash command. The AddOn registers SLASH_INTERACT1 and
+// passes the text typed after the command; parsing happens in Rust. Changes
+// last until the UI is reloaded.
+//
+// Parameters:
+//   msg - Text after "/interact", e.g. "range 6" (string)
+//
+// Returns:
+//   Output to show the player, one line per "\n"-separated line (string)
+//
+// Example: InteractCommand("blacklist add 12345")
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractCommand(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    script::command(lua, lua.get_state(), |command| run_command(&command))
+}
+
+/// Run a slash command that needs the client and return its output
+unsafe fn run_command(command: &Command) -> Vec<String> {
+    match command {
+        Command::Status => status_lines(),
+        Command::Dump => dump_lines(),
+        Command::Snapshot => match capture_snapshot() {
+            Ok(path) => vec![format!("Snapshot saved to {}", path.display())],
+            Err(e) => vec![e.to_string()],
+        },
+        Command::Reload => match load_config() {
+            Ok(()) => vec![format!("Reloaded {}", config::CONFIG_FILE)],
+            Err(e) => vec![e.to_string()],
+        },
+        // Run by commands::run
+        _ => Vec::new(),
+    }
+}
+
+/// Lines for `/interact status`
+unsafe fn status_lines() -> Vec<String> {
+    let range = *RANGE.read().unwrap_or_else(PoisonError::into_inner);
+    let facing = *FACING.read().unwrap_or_else(PoisonError::into_inner);
+    let los = *LINE_OF_SIGHT.read().unwrap_or_else(PoisonError::into_inner);
+    let radii = OBJECT_RADII
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .len();
+    let priority = PRIORITY.read().unwrap_or_else(PoisonError::into_inner);
+    let filter = name_filter();
+    let state = game::get_world_state();
+
+    let mut lines = vec![
+        format!(
+            "Interact v{}: {}",
+            crate::hooks::VERSION,
+            if state.can_interact() {
+                "ready"
+            } else {
+                "cannot interact right now"
+            }
+        ),
+        commands::describe_range(&range),
+        commands::describe_priority(&priority),
+        commands::describe_blacklist(),
+    ];
+    if radii > 0 {
+        lines.push(format!("Object radii: {radii} entries"));
+    }
+    if facing.is_enabled() {
+        lines.push(format!(
+            "Facing: cone {:.0} degrees, weight {:.1} yd",
+            facing.cone.to_degrees() * 2.0,
+            facing.weight
+        ));
+    }
+    lines.push(if los.enabled {
+        format!("Line of sight: on, up to {} checks", los.max_checks)
+    } else {
+        "Line of sight: off".to_owned()
+    });
+    if !filter.is_empty() {
+        lines.push(format!(
+            "Name filters: include [{}], exclude [{}]",
+            filter.include().join(", "),
+            filter.exclude().join(", ")
+        ));
+    }
+    lines.push(format!(
+        "Trace logging {}",
+        commands::on_off(crate::logging::trace_enabled())
+    ));
+    lines
+}
+
+/// Lines for `/interact dump`: objects in range, nearest first
+unsafe fn dump_lines() -> Vec<String> {
+    // Nothing can be read at the login screen or while loading, when the
+    // object manager is null
+    let state = game::get_world_state();
+    if !state.in_world || state.loading {
+        return vec!["Not in world".to_owned()];
+    }
+
+    let world = game::world();
+    let Some(player) =
+        game::get_object_pointer(world.player_guid()).and_then(|pointer| pointer.as_unit())
+    else {
+        return vec!["Not in world".to_owned()];
+    };
+    let player_pos = player.position();
+    let range = *RANGE.read().unwrap_or_else(PoisonError::into_inner);
+    let radii = OBJECT_RADII.read().unwrap_or_else(PoisonError::into_inner);
+
+    let mut found: Vec<(f32, String)> = Vec::new();
+    for (_, object) in world.objects() {
+        let Ok((position, radius)) = selection::candidate_bounds(&*world, &range, &radii, object)
+        else {
+            continue;
+        };
+        let Some(distance) = range.measure(&player_pos, &position, radius) else {
+            continue;
+        };
+
+        let name = object.name().unwrap_or_else(|| "<unnamed>".to_owned());
+        let kind = match object {
+            WowObject::GameObject(go) => format!("object {}", go.entry_id()),
+            _ => "unit".to_owned(),
+        };
+        found.push((distance, format!("{distance:.1} yd  {name} ({kind})")));
+    }
+
+    if found.is_empty() {
+        return vec![format!("Nothing within {:.1} yd", range.max_distance)];
+    }
+    found.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let total = found.len();
+    let mut lines: Vec<String> = found
+        .into_iter()
+        .take(DUMP_LIMIT)
+        .map(|(_, line)| line)
+        .collect();
+    if total > DUMP_LIMIT {
+        lines.push(format!("... and {} more", total - DUMP_LIMIT));
+    }
+    lines
+}
+
+// =============================================================================
+// Script_InteractGetVersion
+// =============================================================================
+//
+// Lua: InteractGetVersion()
+//
+// Reports the DLL version and what it supports, so addons can check for an
+// API before calling it.
+//
+// Returns:
+//   version      - DLL version, e.g. "1.2.1" (string)
+//   capabilities - Table with fields:
+//     version   - Same as the first return value (string)
+//     build     - Client build read from WoW.exe, or nil if unknown (number)
+//     offsets   - Client version the memory offsets are for (string)
+//     functions - Set of Lua function names, e.g. functions.InteractNearest
+//     features  - Set of feature flags, e.g. features.lineOfSight
+//
+// Example: local version, caps = InteractGetVersion()
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractGetVersion(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    let l = lua.get_state();
+
+    script::push_version(
+        lua,
+        l,
+        crate::hooks::VERSION,
+        crate::hooks::client_build(),
+        crate::offsets::TABLE_NAME,
+        FUNCTIONS.iter().map(|(name, _)| *name),
+        FEATURES,
+    )
+}
+
+// =============================================================================
+// Script_InteractDiagnostics
+// =============================================================================
+//
+// Lua: InteractDiagnostics()
+//
+// Runs a self-check and writes the report to Logs\interact_debug.log.
+//
+// Returns:
+//   report - Table with fields hooksInitialized, bootstrapHook, scriptHook,
+//            frameHook, luaInitialized (booleans), version, clientBuild,
+//            expectedBuild,
+//            buildMatches, inWorld, canInteract, objectManager (hex string),
+//            objectCount, playerGuid and playerPointer (hex strings),
+//            lastError, configPath, configFound, configStatus, plus lines:
+//            the report as an array of strings
+//
+// Example: for _, line in ipairs(InteractDiagnostics().lines) do print(line) end
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractDiagnostics(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    let l = lua.get_state();
+
+    let report = diagnostics::collect();
+    for line in report.lines() {
+        debug_log!("[DIAG] {}", line);
+    }
+
+    script::push_report(lua, l, &report)
+}
+
+// =============================================================================
+// Script_InteractCaptureSnapshot
+// =============================================================================
+//
+// Lua: InteractCaptureSnapshot()
+//
+// Records the player's position and state and every visible object with its
+// descriptor fields to Logs\interact_snapshot_<date>_<time>.json. Attach the
+// file to a bug report; interact-sim replays it offline.
+//
+// Returns:
+//   Path of the file written (string), or nil and an error message
+//
+// Example: local path, err = InteractCaptureSnapshot()
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractCaptureSnapshot(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    let l = lua.get_state();
+
+    let result = capture_snapshot();
+    match &result {
+        Ok(path) => debug_log!("Snapshot saved to {}", path.display()),
+        Err(e) => debug_log!("Snapshot failed: {}", e),
+    }
+
+    script::push_path(lua, l, &result)
+}
+
+/// Capture the visible objects and write them to `SNAPSHOT_DIR`
+unsafe fn capture_snapshot() -> Result<PathBuf, SnapshotError> {
+    let state = game::get_world_state();
+    let world = game::world();
+    let player_guid = world.player_guid();
+    let player = game::get_object_pointer(player_guid)
+        .and_then(|pointer| pointer.as_unit())
+        .ok_or(SnapshotError::NotInWorld)?;
+    let view = PlayerView {
+        guid: player_guid,
+        position: player.position(),
+        facing: player.facing(),
+    };
+
+    let build =
+        crate::hooks::client_build().map_or_else(|| "unknown".to_owned(), |b| b.to_string());
+    let description = format!(
+        "Captured by Interact v{} on client build {build}",
+        crate::hooks::VERSION
+    );
+    let snapshot = Snapshot::capture(&*world, &view, state, description);
+    debug_log!("Captured {} objects", snapshot.objects.len());
+    snapshot.write(Path::new(SNAPSHOT_DIR), &crate::logging::file_timestamp())
+}
+
+// =============================================================================
+// Script_InteractGetHistory
+// =============================================================================
+//
+// Lua: InteractGetHistory(n)
+//
+// Lists the interactions InteractNearest performed, newest first. The
+// outcome is inferred from the loot window or error message that follows
+// within a second; until then it is "pending".
+//
+// Parameters:
+//   n - Number of interactions to return, all if nil (number, optional)
+//
+// Returns:
+//   history - Array of tables with fields time (seconds, as GetTime()),
+//             guid (hex string), entry, category (tier name, or nil),
+//             distance (yards), outcome ("loot", "error", "none" or
+//             "pending") and message (the error text, for errors)
+//
+// Example: local last = InteractGetHistory(1)[1]
+
+#[no_mangle]
+pub unsafe extern "fastcall" fn Script_InteractGetHistory(_lua_state: LuaState) -> c_int {
+    let lua = lua::api();
+    let l = lua.get_state();
+
+    script::get_history(lua, l, history::recent)
+}
+
+// =============================================================================
+// Function Registration
+// =============================================================================
+
+/// Lua functions provided by the DLL, in registration order
+const FUNCTIONS: &[(&std::ffi::CStr, lua::LuaCFunction)] = &[
+    (c"InteractNearest", Script_InteractNearest),
+    (c"InteractSetNameFilters", Script_InteractSetNameFilters),
+    (c"InteractSetRange", Script_InteractSetRange),
+    (c"InteractGetRange", Script_InteractGetRange),
+    (c"InteractSetObjectRadius", Script_InteractSetObjectRadius),
+    (c"InteractSetFacing", Script_InteractSetFacing),
+    (c"InteractSetLineOfSight", Script_InteractSetLineOfSight),
+    (c"InteractSetPriority", Script_InteractSetPriority),
+    (c"InteractSetBlacklist", Script_InteractSetBlacklist),
+    (c"InteractSetTrace", Script_InteractSetTrace),
+    (c"InteractReloadConfig", Script_InteractReloadConfig),
+    (c"InteractCommand", Script_InteractCommand),
+    (c"InteractGetVersion", Script_InteractGetVersion),
+    (c"InteractDiagnostics", Script_InteractDiagnostics),
+    (c"InteractCaptureSnapshot", Script_InteractCaptureSnapshot),
+    (c"InteractGetHistory", Script_InteractGetHistory),
+];
+
+/// Feature flags reported by `InteractGetVersion`, for behaviour that has no
+/// function of its own
+const FEATURES: &[&std::ffi::CStr] = &[
+    c"config",
+    c"slashCommands",
+    c"rangeModel",
+    c"facing",
+    c"lineOfSight",
+    c"nameFilters",
+    c"worldState",
+];
+
+/// Register all Lua functions with the game
+pub unsafe fn register_functions() {
+    let lua = lua::api();
+
+    for (name, function) in FUNCTIONS {
+        lua.register_function(name.as_ptr(), *function as *const c_void);
+    }
+
+    debug_log!("Registered {} Lua functions", FUNCTIONS.len());
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // Capability tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_function_names_are_unique() {
+        for (i, (name, _)) in FUNCTIONS.iter().enumerate() {
+            assert!(
+                FUNCTIONS[..i].iter().all(|(other, _)| other != name),
+                "{name:?} registered twice"
+            );
+        }
+    }
+
+    #[test]
+    fn test_function_names_are_valid_lua_identifiers() {
+        for name in FUNCTIONS.iter().map(|(name, _)| name).chain(FEATURES) {
+            let name = name.to_str().unwrap();
+            assert!(name.chars().all(|c| c.is_ascii_alphanumeric()), "{name}");
+            assert!(!name.starts_with(|c: char| c.is_ascii_digit()), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_get_version_is_advertised() {
+        assert!(FUNCTIONS
+            .iter()
+            .any(|(name, _)| *name == c"InteractGetVersion"));
+    }
+}